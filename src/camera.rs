@@ -0,0 +1,58 @@
+//! Items related to positioning and projecting a virtual camera within a 3D scene.
+
+use crate::geom;
+use crate::math::{cgmath, Matrix4, Rad};
+
+/// The distance from the centre of the bounds at which the camera is placed when framing bounds
+/// that have no size.
+pub const DEFAULT_DISTANCE: f32 = 10.0;
+
+/// Produce a view-projection matrix that fits the given `bounds` entirely within view.
+///
+/// The camera is placed along the positive *z* axis from the centre of the bounds, looking
+/// towards the centre with *y* up. It is positioned so that the sphere enclosing the bounds fits
+/// within both the vertical field of view `fov` (in radians) and the horizontal field of view
+/// implied by `aspect` (width / height).
+///
+/// The near and far planes are fitted to the bounding sphere and the resulting matrix produces
+/// depth in the range `0.0..1.0` as expected by wgpu.
+///
+/// If the bounds have no size, the camera is placed `DEFAULT_DISTANCE` from their centre.
+pub fn frame_bounds(bounds: geom::Cuboid<f32>, aspect: f32, fov: f32) -> Matrix4<f32> {
+    let centre = bounds.xyz();
+    let (w, h, d) = (bounds.w(), bounds.h(), bounds.d());
+    let radius = (w * w + h * h + d * d).sqrt() * 0.5;
+
+    // Fit the bounding sphere to the narrower of the vertical and horizontal fields of view.
+    let half_fov_y = fov * 0.5;
+    let half_fov_x = (half_fov_y.tan() * aspect).atan();
+    let half_fov = half_fov_y.min(half_fov_x);
+    let distance = if radius > std::f32::EPSILON {
+        radius / half_fov.sin()
+    } else {
+        DEFAULT_DISTANCE
+    };
+
+    // Leave a little room between the bounds and the clipping planes.
+    let near = (distance - radius) * 0.9;
+    let far = (distance + radius) * 1.1;
+
+    let target = cgmath::Point3::new(centre.x, centre.y, centre.z);
+    let eye = cgmath::Point3::new(centre.x, centre.y, centre.z + distance);
+    let up = cgmath::Vector3::new(0.0, 1.0, 0.0);
+    let view = Matrix4::look_at(eye, target, up);
+    let proj = cgmath::perspective(Rad(fov), aspect, near, far);
+    opengl_to_wgpu_matrix() * proj * view
+}
+
+/// Converts from the OpenGL clip space produced by `cgmath` projections, where *z* ranges from
+/// `-1.0` to `1.0`, to the wgpu clip space, where *z* ranges from `0.0` to `1.0`.
+#[rustfmt::skip]
+pub fn opengl_to_wgpu_matrix() -> Matrix4<f32> {
+    Matrix4::new(
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 0.5, 0.0,
+        0.0, 0.0, 0.5, 1.0,
+    )
+}
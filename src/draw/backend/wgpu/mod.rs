@@ -1,25 +1,198 @@
+use crate::color::{self, LinSrgba};
 use crate::draw;
 use crate::frame::Frame;
+use crate::geom;
 use crate::math::{BaseFloat, NumCast};
 use crate::wgpu;
 
 /// A helper type aimed at simplifying the rendering of conrod primitives via wgpu.
 #[derive(Debug)]
 pub struct Renderer {
-    _vs_mod: wgpu::ShaderModule,
-    _fs_mod: wgpu::ShaderModule,
+    vs_mod: wgpu::ShaderModule,
+    fs_mod: wgpu::ShaderModule,
     render_pipeline: wgpu::RenderPipeline,
+    color_format: wgpu::TextureFormat,
     depth_texture: wgpu::Texture,
     depth_texture_view: wgpu::TextureView,
     bind_group_layout: wgpu::BindGroupLayout,
     bind_group: wgpu::BindGroup,
     vertices: Vec<Vertex>,
     indices: Vec<u32>,
+    vertex_buffer: wgpu::Buffer,
+    vertex_buffer_capacity: usize,
+    index_buffer: wgpu::Buffer,
+    index_buffer_capacity: usize,
+    staging_buffer: StagingBuffer,
+    fixed_size: bool,
+    handedness: Handedness,
+    pixel_perfect: bool,
+    blend_constant: Option<wgpu::Color>,
+    blend_mode: BlendMode,
+    copyable_depth: bool,
+    depth_compare: wgpu::CompareFunction,
+    sample_mask: u32,
+    linear_depth: Option<LinearDepth>,
+    accumulator: Option<Accumulator>,
+    color_readback: bool,
+    readback: Option<ColorReadback>,
+    oit_mode: OitMode,
+    oit: Option<Oit>,
+    sdf: Option<Sdf>,
+}
+
+// The resources used to output linear depth to a secondary color attachment.
+#[derive(Debug)]
+struct LinearDepth {
+    fs_mod: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    render_pipeline: wgpu::RenderPipeline,
+}
+
+// The resources used to render drawings with weighted blended order-independent transparency.
+#[derive(Debug)]
+struct Oit {
+    depth_format: wgpu::TextureFormat,
+    depth_compare: wgpu::CompareFunction,
+    _fs_mod: wgpu::ShaderModule,
+    _resolve_fs_mod: wgpu::ShaderModule,
+    accum_pipeline: wgpu::RenderPipeline,
+    resolve_bind_group_layout: wgpu::BindGroupLayout,
+    resolve_pipeline: wgpu::RenderPipeline,
+    sampler: wgpu::Sampler,
+    quad_vertex_buffer: wgpu::Buffer,
+    targets: Option<OitTargets>,
+}
+
+// The accumulation and revealage targets, recreated when the output attachment size changes.
+#[derive(Debug)]
+struct OitTargets {
+    accum: wgpu::Texture,
+    accum_view: wgpu::TextureView,
+    _revealage: wgpu::Texture,
+    revealage_view: wgpu::TextureView,
+    resolve_bind_group: wgpu::BindGroup,
+}
+
+// The resources used to generate signed distance fields from the coverage of a drawing.
+#[derive(Debug)]
+struct Sdf {
+    _fs_mod: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    sampler: wgpu::Sampler,
+    quad_vertex_buffer: wgpu::Buffer,
+}
+
+// A copy of the most recently rendered texture, retained for reading back colors.
+#[derive(Debug)]
+struct ColorReadback {
+    texture: wgpu::Texture,
+    scale_factor: f32,
+}
+
+// A persistent buffer through which vertices and indices are copied to the GPU.
+//
+// Regions are allocated sequentially so that data yet to be copied by commands that have not been
+// submitted is never overwritten. Once full, the buffer is replaced with one of at least the same
+// capacity and allocation begins again from the start.
+#[derive(Debug)]
+struct StagingBuffer {
+    buffer: wgpu::Buffer,
+    capacity: wgpu::BufferAddress,
+    cursor: wgpu::BufferAddress,
+}
+
+// The render pipelines used to accumulate drawings into a persistent texture.
+#[derive(Debug)]
+struct Accumulator {
+    format: wgpu::TextureFormat,
+    decay_pipeline: wgpu::RenderPipeline,
+    additive_pipeline: wgpu::RenderPipeline,
+    quad_vertex_buffer: wgpu::Buffer,
+}
+
+// The geometry encoded by a render pass.
+//
+// A live **Draw** is read directly so that its mesh is not copied each frame. Recorded
+// **DrawCommands** are only used when replaying via `Renderer::render_commands`.
+enum Source<'a, S> {
+    Draw(&'a draw::Draw<S>),
+    Commands(&'a draw::DrawCommands<S>),
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct LinearDepthUniforms {
+    depth_scale: f32,
 }
 
 #[derive(Debug)]
 pub struct DrawError;
 
+/// An estimate of the GPU memory consumed by the resources of a **Renderer**.
+///
+/// See `Renderer::estimated_memory_usage`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    /// The size of the vertex buffer allocation, i.e. its capacity × vertex size, in bytes.
+    pub vertex_bytes: u64,
+    /// The size of the index buffer allocation, i.e. its capacity × index size, in bytes.
+    pub index_bytes: u64,
+    /// The size of the staging buffer through which vertices and indices are uploaded, in bytes.
+    pub staging_bytes: u64,
+    /// The size of the renderer's depth texture, including all samples.
+    pub depth_bytes: u64,
+}
+
+/// Describes the direction of the *z* axis relative to the viewer when mapping **Draw** vertices
+/// to the output attachment.
+///
+/// In both cases, *x* increases to the right and *y* increases upwards.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Handedness {
+    /// *z* increases away from the viewer, i.e. geometry with a greater *z* is further away.
+    ///
+    /// This is the default.
+    LeftHanded,
+    /// *z* increases towards the viewer, i.e. geometry with a greater *z* is closer.
+    RightHanded,
+}
+
+/// The blend state used when writing a **Draw**ing's geometry to the output attachment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Geometry is blended over the output attachment by the alpha of each fragment.
+    ///
+    /// This is the default.
+    Alpha,
+    /// Geometry is blended over the output attachment by the renderer's blend constant, ignoring
+    /// the alpha of each fragment.
+    ///
+    /// Each channel is written as `src * constant + dst * (1.0 - constant)`, e.g. a constant of
+    /// `0.5` cross-fades the drawing with the existing contents of the output attachment 50/50.
+    /// See `Renderer::set_blend_constant`.
+    Constant,
+}
+
+/// The technique used to render the translucent geometry of a **Draw**ing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OitMode {
+    /// Geometry is blended over the output attachment in the order in which it was drawn.
+    ///
+    /// This is the default.
+    Disabled,
+    /// Geometry is composited using weighted blended order-independent transparency.
+    ///
+    /// All geometry is accumulated into a floating point target weighted by its depth and alpha,
+    /// while the remaining coverage is accumulated into a revealage target. A resolve pass then
+    /// composites the weighted average color over the output attachment. The result is
+    /// independent of the order in which geometry is drawn, at the cost of only approximating
+    /// the correct result where many layers overlap. See `oit_composite`.
+    WeightedBlended,
+}
+
 /// The `Vertex` type passed to the vertex shader.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
@@ -82,6 +255,14 @@ impl wgpu::VertexDescriptor for Vertex {
 }
 
 impl Vertex {
+    /// The distance along *z* within the coordinate space of a **Draw** that is mapped to the
+    /// depth range `0.0..=1.0`.
+    ///
+    /// This is independent of the size and scale factor of the output attachment, so depth is
+    /// consistent as the window is resized. Geometry with a *z* outside of `0.0..=DEPTH_RANGE`
+    /// (or `-DEPTH_RANGE..=0.0` for `Handedness::RightHanded`) is clipped.
+    pub const DEPTH_RANGE: f32 = 1_000.0;
+
     /// Create a vertex from the given mesh vertex.
     pub fn from_mesh_vertex<S>(
         v: draw::mesh::Vertex<S>,
@@ -100,7 +281,7 @@ impl Vertex {
         // In wgpu, *y* increases in the downwards direction, so we negate it.
         let x = 2.0 * x_f * dpi_factor / framebuffer_width;
         let y = -(2.0 * y_f * dpi_factor / framebuffer_height);
-        let z = 2.0 * z_f * dpi_factor / framebuffer_height;
+        let z = z_f / Self::DEPTH_RANGE;
         let tex_x = NumCast::from(v.tex_coords.x).unwrap();
         let tex_y = NumCast::from(v.tex_coords.y).unwrap();
         let position = [x, y, z];
@@ -113,11 +294,159 @@ impl Vertex {
             tex_coords,
         }
     }
+
+    /// Convert the vertex back into a mesh vertex.
+    ///
+    /// This is the inverse of `from_mesh_vertex`, mapping the position from normalised device
+    /// coordinates back to the draw coordinate space of the given framebuffer.
+    pub fn to_mesh_vertex(
+        &self,
+        framebuffer_width: f32,
+        framebuffer_height: f32,
+        dpi_factor: f32,
+    ) -> draw::mesh::Vertex<f32> {
+        let [x, y, z] = self.position;
+        let x_f = x * framebuffer_width / (2.0 * dpi_factor);
+        let y_f = -y * framebuffer_height / (2.0 * dpi_factor);
+        let z_f = z * Self::DEPTH_RANGE;
+        let point = [x_f, y_f, z_f].into();
+        let [r, g, b, a] = self.color;
+        let color = draw::mesh::vertex::Color::new(r, g, b, a);
+        let [tex_x, tex_y] = self.tex_coords;
+        let tex_coords = [tex_x, tex_y].into();
+        draw::mesh::vertex::new(point, color, tex_coords)
+    }
+}
+
+impl BlendMode {
+    // The color state for the output attachment of the given format.
+    fn color_state(&self, format: wgpu::TextureFormat) -> wgpu::ColorStateDescriptor {
+        let (color_blend, alpha_blend) = match *self {
+            BlendMode::Alpha => (
+                wgpu::RenderPipelineBuilder::DEFAULT_COLOR_BLEND,
+                wgpu::RenderPipelineBuilder::DEFAULT_ALPHA_BLEND,
+            ),
+            BlendMode::Constant => {
+                let blend = wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::BlendColor,
+                    dst_factor: wgpu::BlendFactor::OneMinusBlendColor,
+                    operation: wgpu::BlendOperation::Add,
+                };
+                (blend.clone(), blend)
+            }
+        };
+        wgpu::ColorStateDescriptor {
+            format,
+            color_blend,
+            alpha_blend,
+            write_mask: wgpu::RenderPipelineBuilder::DEFAULT_COLOR_WRITE,
+        }
+    }
+}
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        BlendMode::Alpha
+    }
+}
+
+impl Default for OitMode {
+    fn default() -> Self {
+        OitMode::Disabled
+    }
+}
+
+impl MemoryUsage {
+    /// The sum of all estimated memory usage in bytes.
+    pub fn total(&self) -> u64 {
+        self.vertex_bytes + self.index_bytes + self.staging_bytes + self.depth_bytes
+    }
+}
+
+impl Handedness {
+    // The sign applied to the *z* coordinate of each vertex.
+    fn z_sign(&self) -> f32 {
+        match *self {
+            Handedness::LeftHanded => 1.0,
+            Handedness::RightHanded => -1.0,
+        }
+    }
+}
+
+impl Default for Handedness {
+    fn default() -> Self {
+        Handedness::LeftHanded
+    }
+}
+
+impl<'a, S> Clone for Source<'a, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, S> Copy for Source<'a, S> {}
+
+impl StagingBuffer {
+    // The minimum number of uploads of the same size that fit before the buffer is replaced.
+    const MIN_UPLOADS: wgpu::BufferAddress = 4;
+
+    fn new(device: &wgpu::Device, capacity: wgpu::BufferAddress) -> Self {
+        let usage = wgpu::BufferUsage::MAP_WRITE | wgpu::BufferUsage::COPY_SRC;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            size: capacity,
+            usage,
+        });
+        StagingBuffer {
+            buffer,
+            capacity,
+            cursor: 0,
+        }
+    }
+
+    // Allocate a region of `size` bytes, returning its offset.
+    //
+    // Regions are never reused within the same buffer, as copies from earlier regions may not
+    // have been submitted yet. The replaced buffer is kept alive by wgpu until those copies are
+    // complete.
+    fn allocate(
+        &mut self,
+        device: &wgpu::Device,
+        size: wgpu::BufferAddress,
+    ) -> wgpu::BufferAddress {
+        if self.cursor + size > self.capacity {
+            let capacity = (size * Self::MIN_UPLOADS).next_power_of_two();
+            *self = Self::new(device, self.capacity.max(capacity));
+        }
+        let offset = self.cursor;
+        self.cursor += size;
+        offset
+    }
 }
 
 impl Renderer {
     /// The default depth format
     pub const DEFAULT_DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+    /// The function used to compare fragment depth against the depth texture by default.
+    pub const DEFAULT_DEPTH_COMPARE: wgpu::CompareFunction =
+        wgpu::RenderPipelineBuilder::DEFAULT_DEPTH_COMPARE;
+    /// The format of the target into which weighted colors are accumulated when using OIT.
+    pub const OIT_ACCUM_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+    /// The format of the target into which revealage is accumulated when using OIT.
+    pub const OIT_REVEALAGE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+    /// The format of the secondary attachment to which linear depth is written.
+    pub const LINEAR_DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Float;
+    /// The format of the signed distance field textures produced by `render_sdf`.
+    pub const SDF_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Float;
+    /// The maximum distance in pixels searched for an edge when generating a signed distance
+    /// field. Distances are clamped to this value.
+    pub const SDF_SPREAD: u32 = 8;
+    /// The sample mask used by default, enabling all samples.
+    pub const DEFAULT_SAMPLE_MASK: u32 = !0;
+    /// The number of vertices that the vertex buffer can hold before it must first grow.
+    pub const DEFAULT_VERTEX_BUFFER_CAPACITY: usize = 1_024;
+    /// The number of indices that the index buffer can hold before it must first grow.
+    pub const DEFAULT_INDEX_BUFFER_CAPACITY: usize = 3_072;
 
     /// Create a **Renderer** targeting an output attachment texture of the given description.
     pub fn from_texture_descriptor(
@@ -167,13 +496,22 @@ impl Renderer {
         let fs_mod = device.create_shader_module(&fs_spirv);
 
         // Create the depth texture.
-        let depth_texture =
-            create_depth_texture(device, output_attachment_size, depth_format, msaa_samples);
+        let copyable_depth = false;
+        let depth_texture = create_depth_texture(
+            device,
+            output_attachment_size,
+            depth_format,
+            msaa_samples,
+            depth_usage(copyable_depth),
+        );
         let depth_texture_view = depth_texture.create_default_view();
 
         // Create the render pipeline.
         let bind_group_layout = bind_group_layout(device);
         let bind_group = bind_group(device, &bind_group_layout);
+        let depth_compare = Self::DEFAULT_DEPTH_COMPARE;
+        let sample_mask = Self::DEFAULT_SAMPLE_MASK;
+        let blend_mode = BlendMode::default();
         let render_pipeline = render_pipeline(
             device,
             &bind_group_layout,
@@ -181,21 +519,663 @@ impl Renderer {
             &fs_mod,
             output_attachment_color_format,
             depth_format,
+            depth_compare,
             msaa_samples,
+            sample_mask,
+            blend_mode,
         );
         let vertices = vec![];
         let indices = vec![];
+        let vertex_buffer_capacity = Self::DEFAULT_VERTEX_BUFFER_CAPACITY;
+        let vertex_buffer =
+            create_buffer::<Vertex>(device, vertex_buffer_capacity, wgpu::BufferUsage::VERTEX);
+        let index_buffer_capacity = Self::DEFAULT_INDEX_BUFFER_CAPACITY;
+        let index_buffer =
+            create_buffer::<u32>(device, index_buffer_capacity, wgpu::BufferUsage::INDEX);
+        let staging_capacity = vertex_buffer_capacity * std::mem::size_of::<Vertex>()
+            + index_buffer_capacity * std::mem::size_of::<u32>();
+        let staging_buffer = StagingBuffer::new(device, staging_capacity as _);
+        let fixed_size = false;
+        let handedness = Handedness::default();
+        let pixel_perfect = false;
+        let blend_constant = None;
+        let linear_depth = None;
+        let accumulator = None;
+        let color_readback = false;
+        let readback = None;
+        let oit_mode = OitMode::default();
+        let oit = None;
+        let sdf = None;
 
         Self {
-            _vs_mod: vs_mod,
-            _fs_mod: fs_mod,
+            vs_mod,
+            fs_mod,
             render_pipeline,
+            color_format: output_attachment_color_format,
             depth_texture,
             depth_texture_view,
             bind_group_layout,
             bind_group,
             vertices,
             indices,
+            vertex_buffer,
+            vertex_buffer_capacity,
+            index_buffer,
+            index_buffer_capacity,
+            staging_buffer,
+            fixed_size,
+            handedness,
+            pixel_perfect,
+            blend_constant,
+            blend_mode,
+            copyable_depth,
+            depth_compare,
+            sample_mask,
+            linear_depth,
+            accumulator,
+            color_readback,
+            readback,
+            oit_mode,
+            oit,
+            sdf,
+        }
+    }
+
+    /// Specify whether or not the renderer always targets output attachments of the size with
+    /// which it was constructed.
+    ///
+    /// By default, the renderer compares the output attachment size with the size of its depth
+    /// texture on every render pass and recreates the depth texture if they differ. When
+    /// `fixed_size` is `true` this check is skipped.
+    ///
+    /// **Note:** When enabled, it is the caller's responsibility to ensure that the output
+    /// attachment always matches the size the renderer was created with. Rendering to an output
+    /// attachment of a different size will result in a validation error.
+    pub fn fixed_size(mut self, fixed_size: bool) -> Self {
+        self.fixed_size = fixed_size;
+        self
+    }
+
+    /// Specify the handedness of the coordinate system used when mapping vertices.
+    ///
+    /// This determines the sign of the *z* mapping and in turn which geometry occludes which
+    /// during depth testing. By default, `Handedness::LeftHanded` is used.
+    pub fn handedness(mut self, handedness: Handedness) -> Self {
+        self.handedness = handedness;
+        self
+    }
+
+    /// Specify whether or not vertices should be snapped to the centre of the nearest physical
+    /// pixel.
+    ///
+    /// This can be useful for producing crisp 2D UI and pixel art by avoiding sub-pixel vertex
+    /// positions. Snapping is only applied to vertices with a *z* of `0.0` in order to avoid
+    /// distorting 3D geometry.
+    ///
+    /// By default, this is `false`.
+    pub fn pixel_perfect(mut self, pixel_perfect: bool) -> Self {
+        self.pixel_perfect = pixel_perfect;
+        self
+    }
+
+    /// Specify whether or not the renderer's depth texture may be copied from.
+    ///
+    /// This enables the `COPY_SRC` usage of the depth texture, which is required by `read_depth`.
+    /// The depth texture is recreated with the new usage during the next render pass.
+    ///
+    /// By default, this is `false`.
+    pub fn with_copyable_depth(mut self, copyable: bool) -> Self {
+        self.copyable_depth = copyable;
+        self
+    }
+
+    /// Copy the contents of the renderer's depth texture back to the CPU.
+    ///
+    /// The returned depth values are laid out row by row, starting from the top-left of the output
+    /// attachment. This blocks until the GPU has completed the copy and is intended for debugging.
+    ///
+    /// **Panic!**s if the depth texture was not created with copy usage (see
+    /// `with_copyable_depth`), if the depth texture is multisampled or if the depth format is not
+    /// `Depth32Float`.
+    pub fn read_depth(&self, device: &wgpu::Device, queue: &mut wgpu::Queue) -> Vec<f32> {
+        let depth_texture = &self.depth_texture;
+        assert!(
+            depth_texture.usage().contains(wgpu::TextureUsage::COPY_SRC),
+            "the depth texture must be copyable in order to be read, see `with_copyable_depth`",
+        );
+        assert_eq!(
+            depth_texture.sample_count(),
+            1,
+            "multisampled depth textures cannot be read",
+        );
+        assert_eq!(
+            depth_texture.format(),
+            wgpu::TextureFormat::Depth32Float,
+            "only `Depth32Float` depth textures can be read",
+        );
+
+        let desc = wgpu::CommandEncoderDescriptor::default();
+        let mut encoder = device.create_command_encoder(&desc);
+        let buffer = depth_texture.to_buffer_bytes(device, &mut encoder);
+        queue.submit(&[encoder.finish()]);
+
+        let depth = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let depth2 = depth.clone();
+        buffer.read(move |result| {
+            let mapping = result.expect("failed to map depth buffer");
+            let values = mapping.data.chunks_exact(4).map(|bytes| {
+                let bits = [bytes[0], bytes[1], bytes[2], bytes[3]];
+                f32::from_bits(u32::from_ne_bytes(bits))
+            });
+            depth2.borrow_mut().extend(values);
+        });
+        device.poll(true);
+        depth.replace(vec![])
+    }
+
+    /// The number of vertices that the renderer's vertex buffer can hold before it must grow.
+    ///
+    /// The vertex and index buffers are reused between render passes and only grow, to the next
+    /// power of two, when a **Draw**ing exceeds their capacity.
+    pub fn vertex_buffer_capacity(&self) -> usize {
+        self.vertex_buffer_capacity
+    }
+
+    /// The number of indices that the renderer's index buffer can hold before it must grow.
+    pub fn index_buffer_capacity(&self) -> usize {
+        self.index_buffer_capacity
+    }
+
+    /// Grow the vertex and index buffers so that they can hold at least the given number of
+    /// vertices and indices, avoiding reallocation during later render passes.
+    ///
+    /// Buffers that already have sufficient capacity are left untouched.
+    pub fn reserve_buffers(&mut self, device: &wgpu::Device, vertices: usize, indices: usize) {
+        if vertices > self.vertex_buffer_capacity {
+            self.vertex_buffer_capacity = vertices;
+            self.vertex_buffer =
+                create_buffer::<Vertex>(device, vertices, wgpu::BufferUsage::VERTEX);
+        }
+        if indices > self.index_buffer_capacity {
+            self.index_buffer_capacity = indices;
+            self.index_buffer = create_buffer::<u32>(device, indices, wgpu::BufferUsage::INDEX);
+        }
+    }
+
+    /// Specify the technique used to render the translucent geometry of each **Draw**ing.
+    ///
+    /// See `OitMode` for details. The resources required by order-independent transparency are
+    /// created during the next render pass.
+    ///
+    /// **Note:** Order-independent transparency is only supported by renderers with a sample
+    /// count of `1`. The **Draw**ing's background is cleared as usual before all geometry is
+    /// composited over the top.
+    pub fn with_oit(mut self, mode: OitMode) -> Self {
+        self.set_oit(mode);
+        self
+    }
+
+    /// Update the technique used to render translucent geometry.
+    ///
+    /// See `with_oit` for details.
+    pub fn set_oit(&mut self, mode: OitMode) {
+        self.oit_mode = mode;
+        if mode == OitMode::Disabled {
+            self.oit = None;
+        }
+    }
+
+    /// The technique currently used to render translucent geometry.
+    pub fn oit(&self) -> OitMode {
+        self.oit_mode
+    }
+
+    /// Specify whether or not the renderer should retain a copy of the most recently rendered
+    /// texture so that its colors may be read back via `color_under`.
+    ///
+    /// When enabled, `render_to_texture` encodes a copy of the rendered texture into a texture
+    /// owned by the renderer. The rendered texture must have the `COPY_SRC` usage and must not be
+    /// multisampled.
+    ///
+    /// By default, this is `false`.
+    pub fn with_color_readback(mut self, enabled: bool) -> Self {
+        self.color_readback = enabled;
+        if !enabled {
+            self.readback = None;
+        }
+        self
+    }
+
+    /// Read the color of the most recently rendered texture at the given point.
+    ///
+    /// The `point` is in the same logical coordinate space as the **Draw** that was rendered,
+    /// where the origin is the centre of the texture and *y* increases upwards. It is mapped to
+    /// physical pixel coordinates using the scale factor used while rendering. This blocks until
+    /// the GPU has completed the copy and is useful for implementing color pickers.
+    ///
+    /// Returns `None` if no texture has been retained (see `with_color_readback`), if the point
+    /// lies outside of the texture or if the texture's format is not supported. Supported formats
+    /// are the 8-bit `Rgba` and `Bgra` formats and `Rgba16Unorm`.
+    pub fn color_under(
+        &self,
+        device: &wgpu::Device,
+        queue: &mut wgpu::Queue,
+        point: geom::Point2<f32>,
+    ) -> Option<LinSrgba> {
+        let readback = self.readback.as_ref()?;
+        let texture = &readback.texture;
+        let pixel = logical_to_physical_pixel(point, texture.size(), readback.scale_factor)?;
+        let bytes = texture
+            .read_region(device, queue, pixel, [1, 1])
+            .expect("pixel out of bounds");
+        decode_pixel(texture.format(), &bytes)
+    }
+
+    /// Project a point in the coordinate space of a **Draw** to the physical pixel coordinates of
+    /// an output attachment with the given size, as rendered by this renderer.
+    ///
+    /// See the `world_to_screen` function for details. This applies the renderer's handedness.
+    pub fn world_to_screen(
+        &self,
+        point: geom::Point3<f32>,
+        size: [u32; 2],
+        scale_factor: f32,
+    ) -> Option<geom::Point2<f32>> {
+        world_to_screen(point, size, scale_factor, self.handedness)
+    }
+
+    /// Encode a depth-only render pass that clears the renderer's depth texture to `value`,
+    /// leaving all color attachments untouched.
+    ///
+    /// This is useful for layering a HUD over a 3D scene within a single frame, as geometry drawn
+    /// afterwards is no longer occluded by the scene. A `value` of `1.0` is the far plane.
+    ///
+    /// **Note:** Each render pass encoded by the renderer using its own depth texture also begins
+    /// by clearing depth to `1.0`, so this is only necessary when the cleared depth must be
+    /// observed before the next pass, e.g. via `read_depth`, or when a different value is
+    /// required.
+    pub fn clear_depth(&mut self, encoder: &mut wgpu::CommandEncoder, value: f32) {
+        let _render_pass = wgpu::RenderPassBuilder::new()
+            .depth_stencil_attachment(&self.depth_texture_view, |depth| depth.clear_depth(value))
+            .begin(encoder);
+    }
+
+    /// An estimate of the GPU memory consumed by the renderer's vertex, index and depth resources.
+    ///
+    /// The estimate is computed from the allocated capacities and formats of the resources and
+    /// does not account for any padding or alignment applied by the driver. The short-lived
+    /// buffers created to upload uniforms during each render pass are not included.
+    pub fn estimated_memory_usage(&self) -> MemoryUsage {
+        let vertex_bytes = (self.vertex_buffer_capacity * std::mem::size_of::<Vertex>()) as u64;
+        let index_bytes = (self.index_buffer_capacity * std::mem::size_of::<u32>()) as u64;
+        let staging_bytes = self.staging_buffer.capacity;
+        let [w, h] = self.depth_texture.size();
+        let format_bytes = wgpu::texture_format_size_bytes(self.depth_texture.format());
+        let samples = self.depth_texture.sample_count();
+        let depth_bytes = w as u64 * h as u64 * format_bytes as u64 * samples as u64;
+        MemoryUsage {
+            vertex_bytes,
+            index_bytes,
+            staging_bytes,
+            depth_bytes,
+        }
+    }
+
+    /// Specify the constant color used by blend states with the `BlendColor` or
+    /// `OneMinusBlendColor` blend factors.
+    ///
+    /// The constant is set on each render pass encoded by the renderer. It only takes effect when
+    /// the renderer's blend mode is `BlendMode::Constant`, allowing for cheap whole-scene effects
+    /// like cross-fades. Specify `None` to leave the blend constant unset.
+    pub fn set_blend_constant(&mut self, color: Option<wgpu::Color>) {
+        self.blend_constant = color;
+    }
+
+    /// The constant color used by blend states with constant blend factors, if any.
+    pub fn blend_constant(&self) -> Option<wgpu::Color> {
+        self.blend_constant
+    }
+
+    /// Specify how the renderer's geometry is blended with the output attachment.
+    ///
+    /// By default, this is `BlendMode::Alpha`.
+    pub fn with_blend_mode(mut self, device: &wgpu::Device, mode: BlendMode) -> Self {
+        self.set_blend_mode(device, mode);
+        self
+    }
+
+    /// Switch how the renderer's geometry is blended with the output attachment.
+    ///
+    /// This rebuilds the render pipeline's color state, preserving all other state. The blend
+    /// mode is applied to the primary render pipeline, including when writing linear depth, and to
+    /// geometry drawn via `render_to_accumulator`. Translucent geometry rendered with
+    /// `OitMode::WeightedBlended` is composited with its own blend state, so the mode has no
+    /// effect there.
+    ///
+    /// Does nothing if `mode` is equal to the current blend mode.
+    pub fn set_blend_mode(&mut self, device: &wgpu::Device, mode: BlendMode) {
+        if self.blend_mode == mode {
+            return;
+        }
+        self.blend_mode = mode;
+        self.render_pipeline = render_pipeline(
+            device,
+            &self.bind_group_layout,
+            &self.vs_mod,
+            &self.fs_mod,
+            self.color_format,
+            self.depth_format(),
+            self.depth_compare,
+            self.sample_count(),
+            self.sample_mask,
+            mode,
+        );
+        self.rebuild_linear_depth_pipeline(device);
+        self.accumulator = None;
+    }
+
+    /// How the renderer's geometry is blended with the output attachment.
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Switch the number of MSAA samples expected of the output attachment.
+    ///
+    /// This rebuilds only the render pipeline and the depth texture for the new sample count,
+    /// preserving all other state. This is useful in the case that the target window is recreated
+    /// with a different MSAA setting.
+    ///
+    /// Does nothing if `msaa_samples` is equal to the current sample count.
+    pub fn set_sample_count(&mut self, device: &wgpu::Device, msaa_samples: u32) {
+        if self.sample_count() == msaa_samples {
+            return;
+        }
+        let depth_size = self.depth_texture.size();
+        let depth_format = self.depth_texture.format();
+        let depth_usage = self.depth_texture.usage();
+        self.depth_texture =
+            create_depth_texture(device, depth_size, depth_format, msaa_samples, depth_usage);
+        self.depth_texture_view = self.depth_texture.create_default_view();
+        self.render_pipeline = render_pipeline(
+            device,
+            &self.bind_group_layout,
+            &self.vs_mod,
+            &self.fs_mod,
+            self.color_format,
+            depth_format,
+            self.depth_compare,
+            msaa_samples,
+            self.sample_mask,
+            self.blend_mode,
+        );
+        self.rebuild_linear_depth_pipeline(device);
+        self.accumulator = None;
+    }
+
+    /// The number of MSAA samples expected of the output attachment.
+    pub fn sample_count(&self) -> u32 {
+        self.depth_texture.sample_count()
+    }
+
+    /// Switch the format of the renderer's depth texture.
+    ///
+    /// This rebuilds the depth texture and the render pipeline's depth-stencil state for the new
+    /// format, preserving all other state. E.g. switching from `Depth32Float` to
+    /// `Depth24PlusStencil8` allows for stencil operations.
+    ///
+    /// Does nothing if `depth_format` is equal to the current depth format.
+    ///
+    /// **Panic!**s if the given `depth_format` is not a depth or depth-stencil format.
+    pub fn set_depth_format(&mut self, device: &wgpu::Device, depth_format: wgpu::TextureFormat) {
+        assert!(
+            is_depth_format(depth_format),
+            "`{:?}` is not a depth or depth-stencil format",
+            depth_format,
+        );
+        if self.depth_format() == depth_format {
+            return;
+        }
+        let depth_size = self.depth_texture.size();
+        let sample_count = self.depth_texture.sample_count();
+        let depth_usage = self.depth_texture.usage();
+        self.depth_texture =
+            create_depth_texture(device, depth_size, depth_format, sample_count, depth_usage);
+        self.depth_texture_view = self.depth_texture.create_default_view();
+        self.render_pipeline = render_pipeline(
+            device,
+            &self.bind_group_layout,
+            &self.vs_mod,
+            &self.fs_mod,
+            self.color_format,
+            depth_format,
+            self.depth_compare,
+            sample_count,
+            self.sample_mask,
+            self.blend_mode,
+        );
+        self.rebuild_linear_depth_pipeline(device);
+        self.accumulator = None;
+    }
+
+    /// The format of the renderer's depth texture.
+    pub fn depth_format(&self) -> wgpu::TextureFormat {
+        self.depth_texture.format()
+    }
+
+    /// Specify the function used to compare the depth of each fragment against the depth texture.
+    ///
+    /// E.g. `LessEqual` allows coplanar geometry such as decals drawn later to pass the depth
+    /// test, `Greater` suits a reversed depth range and `Always` disables depth rejection.
+    ///
+    /// By default, this is `DEFAULT_DEPTH_COMPARE`.
+    pub fn with_depth_compare(
+        mut self,
+        device: &wgpu::Device,
+        compare: wgpu::CompareFunction,
+    ) -> Self {
+        self.set_depth_compare(device, compare);
+        self
+    }
+
+    /// Switch the function used to compare the depth of each fragment against the depth texture.
+    ///
+    /// This rebuilds the render pipeline's depth-stencil state, preserving all other state.
+    ///
+    /// Does nothing if `compare` is equal to the current depth compare function.
+    pub fn set_depth_compare(&mut self, device: &wgpu::Device, compare: wgpu::CompareFunction) {
+        if self.depth_compare == compare {
+            return;
+        }
+        self.depth_compare = compare;
+        self.render_pipeline = render_pipeline(
+            device,
+            &self.bind_group_layout,
+            &self.vs_mod,
+            &self.fs_mod,
+            self.color_format,
+            self.depth_format(),
+            compare,
+            self.sample_count(),
+            self.sample_mask,
+            self.blend_mode,
+        );
+        self.rebuild_linear_depth_pipeline(device);
+        self.accumulator = None;
+    }
+
+    /// The function used to compare the depth of each fragment against the depth texture.
+    pub fn depth_compare(&self) -> wgpu::CompareFunction {
+        self.depth_compare
+    }
+
+    /// Specify the mask of MSAA samples that may be written by the renderer's geometry.
+    ///
+    /// Each bit enables the sample of the same index within each pixel of a multisampled output
+    /// attachment, e.g. `0b0101` enables half of the samples of a 4x target, producing 50%
+    /// coverage once resolved. This allows for coverage-based effects such as dithered
+    /// transparency. The mask is applied to the primary render pipeline, including when writing
+    /// linear depth, and to geometry drawn via `render_to_accumulator`. Order-independent
+    /// transparency requires a sample count of `1`, so the mask has no effect there.
+    ///
+    /// By default, this is `DEFAULT_SAMPLE_MASK`.
+    pub fn with_sample_mask(mut self, device: &wgpu::Device, mask: u32) -> Self {
+        self.set_sample_mask(device, mask);
+        self
+    }
+
+    /// Switch the mask of MSAA samples that may be written by the renderer's geometry.
+    ///
+    /// This rebuilds the render pipeline, preserving all other state.
+    ///
+    /// Does nothing if `mask` is equal to the current sample mask.
+    pub fn set_sample_mask(&mut self, device: &wgpu::Device, mask: u32) {
+        if self.sample_mask == mask {
+            return;
+        }
+        self.sample_mask = mask;
+        self.render_pipeline = render_pipeline(
+            device,
+            &self.bind_group_layout,
+            &self.vs_mod,
+            &self.fs_mod,
+            self.color_format,
+            self.depth_format(),
+            self.depth_compare,
+            self.sample_count(),
+            mask,
+            self.blend_mode,
+        );
+        self.rebuild_linear_depth_pipeline(device);
+        self.accumulator = None;
+    }
+
+    /// The mask of MSAA samples that may be written by the renderer's geometry.
+    pub fn sample_mask(&self) -> u32 {
+        self.sample_mask
+    }
+
+    /// The fraction of each pixel's samples enabled by `mask` on a target with the given sample
+    /// count, i.e. the coverage that a fully opaque fragment produces once resolved.
+    ///
+    /// Bits beyond `sample_count` are ignored, so `DEFAULT_SAMPLE_MASK` always yields `1.0`.
+    pub fn sample_mask_coverage(mask: u32, sample_count: u32) -> f32 {
+        let sample_count = sample_count.max(1).min(32);
+        let enabled = if sample_count == 32 {
+            mask
+        } else {
+            mask & ((1 << sample_count) - 1)
+        };
+        enabled.count_ones() as f32 / sample_count as f32
+    }
+
+    /// Replace the renderer's vertex and fragment shaders with the given SPIR-V and rebuild the
+    /// render pipeline.
+    ///
+    /// Buffers and textures are preserved. This is useful for live-coding workflows where shaders
+    /// are edited and recompiled while the app is running.
+    ///
+    /// The shaders must be compatible with the renderer's `Vertex` layout and bind group layout.
+    ///
+    /// Returns an error if either of the given byte slices is not valid SPIR-V, in which case the
+    /// existing shaders remain in use.
+    pub fn reload_shaders(
+        &mut self,
+        device: &wgpu::Device,
+        vs_spirv: &[u8],
+        fs_spirv: &[u8],
+    ) -> Result<(), std::io::Error> {
+        let vs_spirv = wgpu::read_spirv(std::io::Cursor::new(vs_spirv))?;
+        let fs_spirv = wgpu::read_spirv(std::io::Cursor::new(fs_spirv))?;
+        self.vs_mod = device.create_shader_module(&vs_spirv);
+        self.fs_mod = device.create_shader_module(&fs_spirv);
+        self.render_pipeline = render_pipeline(
+            device,
+            &self.bind_group_layout,
+            &self.vs_mod,
+            &self.fs_mod,
+            self.color_format,
+            self.depth_format(),
+            self.depth_compare,
+            self.sample_count(),
+            self.sample_mask,
+            self.blend_mode,
+        );
+        self.rebuild_linear_depth_pipeline(device);
+        self.accumulator = None;
+        Ok(())
+    }
+
+    /// Specify whether or not the renderer should support writing linear depth to a secondary
+    /// color attachment.
+    ///
+    /// When enabled, `encode_render_pass_with_linear_depth` may be used to write the *z*
+    /// coordinate of each fragment within the **Draw**'s coordinate space to an attachment of
+    /// format `LINEAR_DEPTH_FORMAT`. Unlike the depth buffer, this attachment may be sampled by
+    /// later passes, e.g. for screen-space ambient occlusion or fog.
+    ///
+    /// By default, this is `false`.
+    pub fn set_linear_depth_output(&mut self, device: &wgpu::Device, enabled: bool) {
+        match (enabled, self.linear_depth.is_some()) {
+            (true, false) => {
+                let fs = include_bytes!("shaders/linear_depth_frag.spv");
+                let fs_spirv = wgpu::read_spirv(std::io::Cursor::new(&fs[..]))
+                    .expect("failed to read hard-coded SPIRV");
+                let fs_mod = device.create_shader_module(&fs_spirv);
+                let bind_group_layout = linear_depth_bind_group_layout(device);
+                let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
+                let uniforms = LinearDepthUniforms { depth_scale: 1.0 };
+                let uniform_buffer = device
+                    .create_buffer_mapped(1, usage)
+                    .fill_from_slice(&[uniforms]);
+                let bind_group =
+                    linear_depth_bind_group(device, &bind_group_layout, &uniform_buffer);
+                let render_pipeline = linear_depth_render_pipeline(
+                    device,
+                    &bind_group_layout,
+                    &self.vs_mod,
+                    &fs_mod,
+                    self.color_format,
+                    self.depth_format(),
+                    self.depth_compare,
+                    self.sample_count(),
+                    self.sample_mask,
+                    self.blend_mode,
+                );
+                self.linear_depth = Some(LinearDepth {
+                    fs_mod,
+                    bind_group_layout,
+                    bind_group,
+                    uniform_buffer,
+                    render_pipeline,
+                });
+            }
+            (false, true) => self.linear_depth = None,
+            _ => (),
+        }
+    }
+
+    /// Whether or not the renderer supports writing linear depth to a secondary attachment.
+    pub fn linear_depth_output(&self) -> bool {
+        self.linear_depth.is_some()
+    }
+
+    // Rebuild the linear depth render pipeline, if any, to match the current renderer state.
+    fn rebuild_linear_depth_pipeline(&mut self, device: &wgpu::Device) {
+        let depth_format = self.depth_format();
+        let sample_count = self.sample_count();
+        if let Some(ref mut linear_depth) = self.linear_depth {
+            linear_depth.render_pipeline = linear_depth_render_pipeline(
+                device,
+                &linear_depth.bind_group_layout,
+                &self.vs_mod,
+                &linear_depth.fs_mod,
+                self.color_format,
+                depth_format,
+                self.depth_compare,
+                sample_count,
+                self.sample_mask,
+                self.blend_mode,
+            );
         }
     }
 
@@ -218,70 +1198,474 @@ impl Renderer {
     ) where
         S: BaseFloat,
     {
+        self.encode_render_pass_inner(
+            device,
+            encoder,
+            Source::Draw(draw),
+            scale_factor,
+            output_attachment_size,
+            output_attachment,
+            resolve_target,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    /// Encode a render pass with the given recorded **DrawCommands** to the given
+    /// `output_attachment`.
+    ///
+    /// This is the same as **encode_render_pass**, but allows for replaying the same commands at
+    /// different output sizes without re-running the drawing code, e.g. rendering both a small
+    /// preview and a high resolution export. See `Draw::record`.
+    pub fn render_commands<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        commands: &draw::DrawCommands<S>,
+        scale_factor: f32,
+        output_attachment_size: [u32; 2],
+        output_attachment: &wgpu::TextureView,
+        resolve_target: Option<&wgpu::TextureView>,
+    ) where
+        S: BaseFloat,
+    {
+        self.encode_render_pass_inner(
+            device,
+            encoder,
+            Source::Commands(commands),
+            scale_factor,
+            output_attachment_size,
+            output_attachment,
+            resolve_target,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    /// The same as **encode_render_pass**, but uses the given `load_op` for the output attachment
+    /// rather than deriving it from the **Draw**ing's background.
+    ///
+    /// This is useful for composing several **Draw**ings into the same texture in multiple passes,
+    /// where the first pass should clear the texture and subsequent passes should load it.
+    ///
+    /// With `LoadOp::Clear`, the attachment is cleared to the background color if one is
+    /// specified, or to transparent otherwise. With `LoadOp::Load`, the existing contents are
+    /// preserved and the **Draw**ing's background color and gradient are ignored.
+    pub fn encode_render_pass_with_load_op<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw<S>,
+        scale_factor: f32,
+        output_attachment_size: [u32; 2],
+        output_attachment: &wgpu::TextureView,
+        resolve_target: Option<&wgpu::TextureView>,
+        load_op: wgpu::LoadOp,
+    ) where
+        S: BaseFloat,
+    {
+        self.encode_render_pass_inner(
+            device,
+            encoder,
+            Source::Draw(draw),
+            scale_factor,
+            output_attachment_size,
+            output_attachment,
+            resolve_target,
+            None,
+            None,
+            None,
+            Some(load_op),
+        )
+    }
+
+    /// The same as **encode_render_pass**, but uses the given `depth_view` as the depth-stencil
+    /// attachment rather than the renderer's internal depth texture.
+    ///
+    /// This is useful for sharing a single depth buffer between multiple passes, e.g. when
+    /// integrating a **Draw** into a larger pipeline. The internal depth texture is neither used
+    /// nor resized.
+    ///
+    /// The `depth_view` must match the size and sample count of the `output_attachment` and the
+    /// depth format that the renderer was created with.
+    pub fn encode_render_pass_with_depth<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw<S>,
+        scale_factor: f32,
+        output_attachment_size: [u32; 2],
+        output_attachment: &wgpu::TextureView,
+        resolve_target: Option<&wgpu::TextureView>,
+        depth_view: &wgpu::TextureView,
+    ) where
+        S: BaseFloat,
+    {
+        self.encode_render_pass_inner(
+            device,
+            encoder,
+            Source::Draw(draw),
+            scale_factor,
+            output_attachment_size,
+            output_attachment,
+            resolve_target,
+            Some(depth_view),
+            None,
+            None,
+            None,
+        )
+    }
+
+    /// The same as **encode_render_pass**, but also writes linear depth to the given
+    /// `linear_depth_attachment`.
+    ///
+    /// The `linear_depth_attachment` must be of format `LINEAR_DEPTH_FORMAT` and must match the
+    /// size and sample count of the `output_attachment`. The attachment is cleared to `0.0` if the
+    /// **Draw**ing specifies a background color or gradient.
+    ///
+    /// **Panic!**s if linear depth output has not been enabled via `set_linear_depth_output`.
+    pub fn encode_render_pass_with_linear_depth<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw<S>,
+        scale_factor: f32,
+        output_attachment_size: [u32; 2],
+        output_attachment: &wgpu::TextureView,
+        resolve_target: Option<&wgpu::TextureView>,
+        linear_depth_attachment: &wgpu::TextureView,
+    ) where
+        S: BaseFloat,
+    {
+        self.encode_render_pass_inner(
+            device,
+            encoder,
+            Source::Draw(draw),
+            scale_factor,
+            output_attachment_size,
+            output_attachment,
+            resolve_target,
+            None,
+            Some(linear_depth_attachment),
+            None,
+            None,
+        )
+    }
+
+    // Shared between the `encode_render_pass` family of methods.
+    //
+    // If no `depth_view` is given, the internal depth texture is used. If a `linear_depth_view` is
+    // given, the linear depth pipeline is used to write to it as a secondary color attachment.
+    //
+    // If a `region` is given as `[x, y, w, h]` in pixels, the **Draw** is mapped to and clipped by
+    // that region of the output attachment and the rest of the attachment is left untouched.
+    //
+    // If a `load_op` is given, it overrides the load operation derived from the background.
+    fn encode_render_pass_inner<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        source: Source<S>,
+        scale_factor: f32,
+        output_attachment_size: [u32; 2],
+        output_attachment: &wgpu::TextureView,
+        resolve_target: Option<&wgpu::TextureView>,
+        depth_view: Option<&wgpu::TextureView>,
+        linear_depth_view: Option<&wgpu::TextureView>,
+        region: Option<[u32; 4]>,
+        load_op: Option<wgpu::LoadOp>,
+    ) where
+        S: BaseFloat,
+    {
+        // Create the order-independent transparency resources if necessary.
+        if self.oit_mode == OitMode::WeightedBlended {
+            self.update_oit(device, output_attachment_size);
+        }
+
         let Renderer {
             ref render_pipeline,
             ref mut vertices,
             ref mut indices,
+            ref mut vertex_buffer,
+            ref mut vertex_buffer_capacity,
+            ref mut index_buffer,
+            ref mut index_buffer_capacity,
+            ref mut staging_buffer,
             ref mut depth_texture,
             ref mut depth_texture_view,
             ref bind_group,
+            fixed_size,
+            handedness,
+            pixel_perfect,
+            blend_constant,
+            copyable_depth,
+            ref linear_depth,
+            ref oit,
             ..
         } = *self;
 
-        // Resize the depth texture if the output attachment size has changed.
-        let depth_size = depth_texture.size();
-        if output_attachment_size != depth_size {
-            let depth_format = depth_texture.format();
-            let sample_count = depth_texture.sample_count();
-            *depth_texture =
-                create_depth_texture(device, output_attachment_size, depth_format, sample_count);
-            *depth_texture_view = depth_texture.create_default_view();
-        }
-
-        // Retrieve the clear values based on the bg color.
-        let bg_color = draw.state.borrow().background_color;
-        let (load_op, clear_color) = match bg_color {
-            None => (wgpu::LoadOp::Load, wgpu::Color::TRANSPARENT),
-            Some(color) => {
+        // Resize the depth texture if the output attachment size has changed or recreate it if
+        // its usage has changed.
+        // This is unnecessary if an external depth view was provided.
+        let depth_view = match depth_view {
+            Some(view) => view,
+            None => {
+                let resized = !fixed_size && output_attachment_size != depth_texture.size();
+                let usage = depth_usage(copyable_depth);
+                if resized || usage != depth_texture.usage() {
+                    let size = match resized {
+                        true => output_attachment_size,
+                        false => depth_texture.size(),
+                    };
+                    let depth_format = depth_texture.format();
+                    let sample_count = depth_texture.sample_count();
+                    *depth_texture =
+                        create_depth_texture(device, size, depth_format, sample_count, usage);
+                    *depth_texture_view = depth_texture.create_default_view();
+                }
+                &*depth_texture_view
+            }
+        };
+
+        // Retrieve the clear values based on the bg color. Clearing would affect the entire
+        // attachment, so the background is ignored when rendering to a region. It is also ignored
+        // when the caller requests that the existing contents be loaded. Gradients are drawn over
+        // a transparent clear so that their alpha is preserved.
+        let load_op_override = load_op;
+        let (bg_color, bg_gradient) = match (region, load_op_override) {
+            (Some(_), _) | (None, Some(wgpu::LoadOp::Load)) => (None, None),
+            (None, _) => match source {
+                Source::Draw(draw) => (draw.background_color(), draw.background_gradient()),
+                Source::Commands(cmds) => (cmds.background_color(), cmds.background_gradient()),
+            },
+        };
+        let (load_op, clear_color) = match (bg_color, bg_gradient) {
+            (_, Some(_)) => (wgpu::LoadOp::Clear, wgpu::Color::TRANSPARENT),
+            (None, None) => (wgpu::LoadOp::Load, wgpu::Color::TRANSPARENT),
+            (Some(color), None) => {
                 let (r, g, b, a) = color.into();
                 let (r, g, b, a) = (r as f64, g as f64, b as f64, a as f64);
                 let clear_color = wgpu::Color { r, g, b, a };
                 (wgpu::LoadOp::Clear, clear_color)
             }
         };
+        let load_op = load_op_override.unwrap_or(load_op);
 
         // Create the vertex and index buffers.
-        let [img_w, img_h] = output_attachment_size;
-        let map_vertex = |v| Vertex::from_mesh_vertex(v, img_w as _, img_h as _, scale_factor);
+        let [img_w, img_h] = match region {
+            None => output_attachment_size,
+            Some([_, _, w, h]) => [w, h],
+        };
+        let z_sign = handedness.z_sign();
+        let map_vertex = |v| {
+            let mut vertex = Vertex::from_mesh_vertex(v, img_w as _, img_h as _, scale_factor);
+            vertex.position[2] *= z_sign;
+            if pixel_perfect && vertex.position[2] == 0.0 {
+                vertex.position[0] = snap_to_pixel_center(vertex.position[0], img_w);
+                vertex.position[1] = snap_to_pixel_center(vertex.position[1], img_h);
+            }
+            vertex
+        };
         vertices.clear();
-        vertices.extend(draw.raw_vertices().map(map_vertex));
-        let vertex_buffer = device
-            .create_buffer_mapped(vertices.len(), wgpu::BufferUsage::VERTEX)
-            .fill_from_slice(&vertices[..]);
         indices.clear();
-        indices.extend(draw.inner_mesh().indices().iter().map(|&u| u as u32));
-        let index_buffer = device
-            .create_buffer_mapped(indices.len(), wgpu::BufferUsage::INDEX)
-            .fill_from_slice(&indices[..]);
+        if let Some(gradient) = bg_gradient {
+            vertices.extend(gradient_vertices(&gradient));
+            indices.extend(0..vertices.len() as u32);
+        }
+        let index_offset = vertices.len() as u32;
+        let map_index = |u: usize| index_offset + u as u32;
+        match source {
+            Source::Draw(draw) => {
+                vertices.extend(draw.raw_vertices().map(map_vertex));
+                draw.with_z_ordered_indices(|draw_indices| {
+                    indices.extend(draw_indices.map(map_index));
+                });
+            }
+            Source::Commands(cmds) => {
+                vertices.extend(cmds.vertices().iter().cloned().map(map_vertex));
+                indices.extend(cmds.indices().iter().cloned().map(map_index));
+            }
+        }
+        upload_to_buffer(
+            device,
+            encoder,
+            staging_buffer,
+            vertex_buffer,
+            vertex_buffer_capacity,
+            vertices,
+            wgpu::BufferUsage::VERTEX,
+        );
+        upload_to_buffer(
+            device,
+            encoder,
+            staging_buffer,
+            index_buffer,
+            index_buffer_capacity,
+            indices,
+            wgpu::BufferUsage::INDEX,
+        );
+        let (vertex_buffer, index_buffer) = (&*vertex_buffer, &*index_buffer);
+
+        // If writing linear depth, upload the scale used to map fragment depth back to *z*.
+        let (render_pipeline, bind_group) = match linear_depth_view {
+            None => (render_pipeline, bind_group),
+            Some(_) => {
+                let linear_depth = linear_depth.as_ref().expect(
+                    "linear depth output is not enabled, see `Renderer::set_linear_depth_output`",
+                );
+                let depth_scale = z_sign * Vertex::DEPTH_RANGE;
+                let uniforms = LinearDepthUniforms { depth_scale };
+                let size = std::mem::size_of::<LinearDepthUniforms>() as wgpu::BufferAddress;
+                let new_uniform_buffer = device
+                    .create_buffer_mapped(1, wgpu::BufferUsage::COPY_SRC)
+                    .fill_from_slice(&[uniforms]);
+                encoder.copy_buffer_to_buffer(
+                    &new_uniform_buffer,
+                    0,
+                    &linear_depth.uniform_buffer,
+                    0,
+                    size,
+                );
+                (&linear_depth.render_pipeline, &linear_depth.bind_group)
+            }
+        };
 
         // Encode the render pass.
-        let mut render_pass = wgpu::RenderPassBuilder::new()
+        let mut render_pass_builder = wgpu::RenderPassBuilder::new()
             .color_attachment(output_attachment, |color| {
                 color
                     .resolve_target(resolve_target)
                     .load_op(load_op)
                     .clear_color(clear_color)
             })
-            .depth_stencil_attachment(&*depth_texture_view, |depth| depth)
-            .begin(encoder);
+            .depth_stencil_attachment(depth_view, |depth| depth);
+        if let Some(view) = linear_depth_view {
+            render_pass_builder = render_pass_builder.color_attachment(view, |color| {
+                color.load_op(load_op).clear_color(wgpu::Color::TRANSPARENT)
+            });
+        }
+        let mut render_pass = render_pass_builder.begin(encoder);
         render_pass.set_pipeline(render_pipeline);
+        if let Some([x, y, w, h]) = region {
+            render_pass.set_viewport(x as f32, y as f32, w as f32, h as f32, 0.0, 1.0);
+            render_pass.set_scissor_rect(x, y, w, h);
+        }
+        if let Some(color) = blend_constant {
+            render_pass.set_blend_color(color);
+        }
         render_pass.set_bind_group(0, bind_group, &[]);
-        render_pass.set_index_buffer(&index_buffer, 0);
-        render_pass.set_vertex_buffers(0, &[(&vertex_buffer, 0)]);
-        let index_range = 0..indices.len() as u32;
+        render_pass.set_index_buffer(index_buffer, 0);
+        render_pass.set_vertex_buffers(0, &[(vertex_buffer, 0)]);
+        // When using OIT, only the background is drawn directly to the output attachment.
+        let index_range = match *oit {
+            None => 0..indices.len() as u32,
+            Some(_) => 0..index_offset,
+        };
         let start_vertex = 0;
         let instance_range = 0..1;
         render_pass.draw_indexed(index_range, start_vertex, instance_range);
+        drop(render_pass);
+
+        let oit = match *oit {
+            None => return,
+            Some(ref oit) => oit,
+        };
+        let targets = oit.targets.as_ref().expect("no OIT targets");
+
+        // Accumulate the weighted colors and revealage of all geometry, testing against the depth
+        // of the background without writing to it.
+        let revealage_clear = wgpu::Color {
+            r: 1.0,
+            g: 1.0,
+            b: 1.0,
+            a: 1.0,
+        };
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(&targets.accum_view, |color| {
+                color.clear_color(wgpu::Color::TRANSPARENT)
+            })
+            .color_attachment(&targets.revealage_view, |color| {
+                color.clear_color(revealage_clear)
+            })
+            .depth_stencil_attachment(depth_view, |depth| {
+                depth
+                    .depth_load_op(wgpu::LoadOp::Load)
+                    .stencil_load_op(wgpu::LoadOp::Load)
+            })
+            .begin(encoder);
+        render_pass.set_pipeline(&oit.accum_pipeline);
+        if let Some([x, y, w, h]) = region {
+            render_pass.set_viewport(x as f32, y as f32, w as f32, h as f32, 0.0, 1.0);
+            render_pass.set_scissor_rect(x, y, w, h);
+        }
+        // The linear depth bind group may be in use above, so bind the renderer's own.
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_index_buffer(index_buffer, 0);
+        render_pass.set_vertex_buffers(0, &[(vertex_buffer, 0)]);
+        let index_range = index_offset..indices.len() as u32;
+        render_pass.draw_indexed(index_range, start_vertex, 0..1);
+        drop(render_pass);
+
+        // Composite the accumulated geometry over the output attachment.
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(output_attachment, |color| {
+                color
+                    .resolve_target(resolve_target)
+                    .load_op(wgpu::LoadOp::Load)
+            })
+            .begin(encoder);
+        render_pass.set_pipeline(&oit.resolve_pipeline);
+        if let Some([x, y, w, h]) = region {
+            render_pass.set_viewport(x as f32, y as f32, w as f32, h as f32, 0.0, 1.0);
+            render_pass.set_scissor_rect(x, y, w, h);
+        }
+        render_pass.set_bind_group(0, &targets.resolve_bind_group, &[]);
+        render_pass.set_vertex_buffers(0, &[(&oit.quad_vertex_buffer, 0)]);
+        let vertex_range = 0..QUAD_VERTICES.len() as u32;
+        render_pass.draw(vertex_range, 0..1);
+    }
+
+    // Create the OIT pipelines if they are missing or out of date and resize the OIT targets to
+    // match the output attachment.
+    fn update_oit(&mut self, device: &wgpu::Device, output_attachment_size: [u32; 2]) {
+        assert_eq!(
+            self.sample_count(),
+            1,
+            "order-independent transparency requires a renderer sample count of 1",
+        );
+        let depth_format = self.depth_format();
+        let depth_compare = self.depth_compare;
+        let up_to_date = match self.oit {
+            Some(ref oit) => oit.depth_format == depth_format && oit.depth_compare == depth_compare,
+            None => false,
+        };
+        if !up_to_date {
+            self.oit = Some(create_oit(
+                device,
+                &self.bind_group_layout,
+                &self.vs_mod,
+                self.color_format,
+                depth_format,
+                depth_compare,
+            ));
+        }
+        let oit = self.oit.as_mut().expect("no OIT resources");
+        let resized = match oit.targets {
+            Some(ref targets) => targets.accum.size() != output_attachment_size,
+            None => true,
+        };
+        if resized {
+            oit.targets = Some(create_oit_targets(
+                device,
+                &oit.resolve_bind_group_layout,
+                &oit.sampler,
+                output_attachment_size,
+            ));
+        }
     }
 
     /// Encode the necessary commands to render the contents of the given **Draw**ing to the given
@@ -294,10 +1678,258 @@ impl Renderer {
         texture: &wgpu::Texture,
     ) where
         S: BaseFloat,
+    {
+        let scale_factor = 1.0;
+        self.render_to_texture_with_scale_factor(device, encoder, draw, scale_factor, texture);
+    }
+
+    /// The same as **render_to_texture**, but scales the **Draw**ing by the given `scale_factor`.
+    ///
+    /// This is useful for baking a **Draw**ing laid out in logical coordinates for a HiDPI window
+    /// into a texture of the window's physical size, e.g. for later compositing.
+    pub fn render_to_texture_with_scale_factor<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw<S>,
+        scale_factor: f32,
+        texture: &wgpu::Texture,
+    ) where
+        S: BaseFloat,
     {
         let size = texture.size();
         let view = texture.create_default_view();
-        // TODO: Should we expose this for rendering to textures?
+        let resolve_target = None;
+        self.encode_render_pass(
+            device,
+            encoder,
+            draw,
+            scale_factor,
+            size,
+            &view,
+            resolve_target,
+        );
+        if self.color_readback {
+            self.retain_readback(device, encoder, texture, scale_factor);
+        }
+    }
+
+    // Encode a copy of the given texture into the renderer's readback texture, recreating the
+    // readback texture if the size or format has changed.
+    fn retain_readback(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+        scale_factor: f32,
+    ) {
+        assert!(
+            texture.usage().contains(wgpu::TextureUsage::COPY_SRC),
+            "the texture must be copyable in order to be retained for color readback",
+        );
+        assert_eq!(
+            texture.sample_count(),
+            1,
+            "multisampled textures cannot be retained for color readback",
+        );
+        let size = texture.size();
+        let format = texture.format();
+        let up_to_date = match self.readback {
+            Some(ref rb) => rb.texture.size() == size && rb.texture.format() == format,
+            None => false,
+        };
+        if !up_to_date {
+            let texture = wgpu::TextureBuilder::new()
+                .size(size)
+                .format(format)
+                .usage(wgpu::TextureUsage::COPY_DST | wgpu::TextureUsage::COPY_SRC)
+                .build(device);
+            self.readback = Some(ColorReadback {
+                texture,
+                scale_factor,
+            });
+        }
+        let readback = self.readback.as_mut().expect("no readback texture");
+        readback.scale_factor = scale_factor;
+        let origin = wgpu::Origin3d {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let src = wgpu::TextureCopyView {
+            texture: texture.inner(),
+            mip_level: 0,
+            array_layer: 0,
+            origin,
+        };
+        let dst = wgpu::TextureCopyView {
+            texture: readback.texture.inner(),
+            mip_level: 0,
+            array_layer: 0,
+            origin,
+        };
+        let [width, height] = size;
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+        encoder.copy_texture_to_texture(src, dst, extent);
+    }
+
+    /// Encode the necessary commands to render the contents of the given **Draw**ing to a region of
+    /// the given **Texture**, e.g. a slot within a texture atlas.
+    ///
+    /// The **Draw**ing is mapped to the `region` as though it were the entire output attachment,
+    /// and the viewport and scissor rect are used so that the rest of the texture is left
+    /// untouched. The `region` is in pixels where the lowest *y* value is the top row of the
+    /// region, matching the layout of texture data.
+    ///
+    /// **Note:** As clearing would affect the entire texture, the **Draw**ing's background color
+    /// is ignored.
+    ///
+    /// **Panic!**s if the `region` does not lie within the bounds of the texture.
+    pub fn render_to_atlas_region<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw<S>,
+        atlas_texture: &wgpu::Texture,
+        region: geom::Rect<u32>,
+    ) where
+        S: BaseFloat,
+    {
+        let size = atlas_texture.size();
+        let (x, y) = (region.left(), region.bottom());
+        let (w, h) = (region.right() - x, region.top() - y);
+        let fits =
+            |start: u32, len: u32, max: u32| start.checked_add(len).map_or(false, |end| end <= max);
+        assert!(
+            fits(x, w, size[0]) && fits(y, h, size[1]),
+            "region {:?} exceeds the bounds of the {:?} texture",
+            region,
+            size,
+        );
+        let view = atlas_texture.create_default_view();
+        let scale_factor = 1.0;
+        let resolve_target = None;
+        self.encode_render_pass_inner(
+            device,
+            encoder,
+            Source::Draw(draw),
+            scale_factor,
+            size,
+            &view,
+            resolve_target,
+            None,
+            None,
+            Some([x, y, w, h]),
+            None,
+        );
+    }
+
+    /// Encode the necessary commands to accumulate the contents of the given **Draw**ing into the
+    /// given `accumulator` texture, e.g. for motion trails and other feedback effects.
+    ///
+    /// The existing contents of the accumulator are first multiplied by `decay`, after which the
+    /// **Draw**ing is added over the top using additive blending. A `decay` of `1.0` preserves the
+    /// previous contents entirely while `0.0` discards them.
+    ///
+    /// The accumulator is typically a floating point texture (e.g. `Rgba16Float`) that persists
+    /// between frames. It must have the `OUTPUT_ATTACHMENT` usage enabled and must have the same
+    /// sample count as the renderer.
+    ///
+    /// **Note:** The **Draw**ing should not specify a background color, as this would clear the
+    /// accumulator.
+    pub fn render_to_accumulator<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw<S>,
+        accumulator: &wgpu::Texture,
+        decay: f32,
+    ) where
+        S: BaseFloat,
+    {
+        assert_eq!(
+            accumulator.sample_count(),
+            self.sample_count(),
+            "the accumulator sample count must match that of the renderer",
+        );
+
+        // Create the accumulation pipelines if necessary.
+        let format = accumulator.format();
+        let up_to_date = match self.accumulator {
+            Some(ref acc) => acc.format == format,
+            None => false,
+        };
+        if !up_to_date {
+            self.accumulator = Some(create_accumulator(
+                device,
+                &self.bind_group_layout,
+                &self.vs_mod,
+                &self.fs_mod,
+                format,
+                self.depth_format(),
+                self.depth_compare,
+                self.sample_count(),
+                self.sample_mask,
+            ));
+        }
+        let mut acc = self.accumulator.take().expect("no accumulator");
+
+        // Multiply the existing contents by the decay factor via the constant blend color.
+        {
+            let view = accumulator.create_default_view();
+            let mut render_pass = wgpu::RenderPassBuilder::new()
+                .color_attachment(&view, |color| color.load_op(wgpu::LoadOp::Load))
+                .begin(encoder);
+            render_pass.set_pipeline(&acc.decay_pipeline);
+            let d = decay as f64;
+            render_pass.set_blend_color(wgpu::Color {
+                r: d,
+                g: d,
+                b: d,
+                a: d,
+            });
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.set_vertex_buffers(0, &[(&acc.quad_vertex_buffer, 0)]);
+            let vertex_range = 0..QUAD_VERTICES.len() as u32;
+            let instance_range = 0..1;
+            render_pass.draw(vertex_range, instance_range);
+        }
+
+        // Draw over the decayed contents using the additive pipeline.
+        std::mem::swap(&mut self.render_pipeline, &mut acc.additive_pipeline);
+        self.render_to_texture(device, encoder, draw, accumulator);
+        std::mem::swap(&mut self.render_pipeline, &mut acc.additive_pipeline);
+        self.accumulator = Some(acc);
+    }
+
+    /// Encode the necessary commands to render the contents of the given **Draw**ing to a single
+    /// array layer of the given **Texture**.
+    ///
+    /// This is useful for rendering to cubemap faces or other layered render targets.
+    ///
+    /// **Panic!**s if `layer` is out of range of the texture's array layers.
+    pub fn render_to_texture_layer<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw<S>,
+        texture: &wgpu::Texture,
+        layer: u32,
+    ) where
+        S: BaseFloat,
+    {
+        assert!(
+            layer < texture.array_layer_count(),
+            "layer {} is out of range for a texture with {} array layers",
+            layer,
+            texture.array_layer_count(),
+        );
+        let size = texture.size();
+        let view = texture.create_view(&texture.create_layer_view_descriptor(layer));
         let scale_factor = 1.0;
         let resolve_target = None;
         self.encode_render_pass(
@@ -311,6 +1943,97 @@ impl Renderer {
         );
     }
 
+    /// Encode the necessary commands to render the contents of the given **Draw**ing as a signed
+    /// distance field, returning a new texture of the given size and format `SDF_FORMAT`.
+    ///
+    /// The **Draw**ing is first rasterized to a coverage texture, where texels with an alpha of at
+    /// least `0.5` are considered inside of the shape. Each texel of the returned texture then
+    /// holds the distance in pixels to the nearest edge, negative inside of the shape and positive
+    /// outside, clamped to `SDF_SPREAD`. The edges of the texture are considered outside of the
+    /// shape. See `signed_distance_field` for the equivalent CPU implementation.
+    ///
+    /// The distance field may be re-rendered at any resolution with a shader that thresholds the
+    /// interpolated distance, e.g. for crisp scalable icons.
+    ///
+    /// **Note:** The **Draw**ing should not specify a background color, as this would cover the
+    /// entire texture.
+    pub fn render_sdf<S>(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw<S>,
+        size: [u32; 2],
+    ) -> wgpu::Texture
+    where
+        S: BaseFloat,
+    {
+        // Rasterize the coverage, resolving it first if the renderer is multisampled.
+        let coverage = wgpu::TextureBuilder::new()
+            .size(size)
+            .format(self.color_format)
+            .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED)
+            .build(device);
+        let coverage_view = coverage.create_default_view();
+        let multisampled = match self.sample_count() {
+            1 => None,
+            sample_count => {
+                let texture = wgpu::TextureBuilder::new()
+                    .size(size)
+                    .format(self.color_format)
+                    .sample_count(sample_count)
+                    .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT)
+                    .build(device);
+                let view = texture.create_default_view();
+                Some((texture, view))
+            }
+        };
+        let (attachment, resolve_target) = match multisampled {
+            None => (&coverage_view, None),
+            Some((_, ref view)) => (view, Some(&coverage_view)),
+        };
+        wgpu::clear_texture(attachment, wgpu::Color::TRANSPARENT, encoder);
+        let scale_factor = 1.0;
+        self.encode_render_pass(
+            device,
+            encoder,
+            draw,
+            scale_factor,
+            size,
+            attachment,
+            resolve_target,
+        );
+
+        // Compute the distance to the nearest edge for every texel.
+        if self.sdf.is_none() {
+            self.sdf = Some(create_sdf(device, &self.vs_mod));
+        }
+        let sdf = self.sdf.as_ref().expect("no sdf pipeline");
+        let texture = wgpu::TextureBuilder::new()
+            .size(size)
+            .format(Self::SDF_FORMAT)
+            .usage(
+                wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                    | wgpu::TextureUsage::SAMPLED
+                    | wgpu::TextureUsage::COPY_SRC,
+            )
+            .build(device);
+        let view = texture.create_default_view();
+        let bind_group = wgpu::BindGroupBuilder::new()
+            .texture_view(&coverage_view)
+            .sampler(&sdf.sampler)
+            .build(device, &sdf.bind_group_layout);
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(&view, |color| color)
+            .begin(encoder);
+        render_pass.set_pipeline(&sdf.pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_vertex_buffers(0, &[(&sdf.quad_vertex_buffer, 0)]);
+        let vertex_range = 0..QUAD_VERTICES.len() as u32;
+        render_pass.draw(vertex_range, 0..1);
+        drop(render_pass);
+        texture
+    }
+
     /// Encode the necessary commands to render the contents of the given **Draw**ing to the given
     /// **Frame**.
     pub fn render_to_frame<S>(
@@ -338,16 +2061,298 @@ impl Renderer {
     }
 }
 
+/// The weight given to a fragment at the given depth when using `OitMode::WeightedBlended`.
+///
+/// This is one of the depth weight functions suggested by McGuire and Bavoil in "Weighted Blended
+/// Order-Independent Transparency", favouring fragments that are nearer to the viewer. The
+/// `depth` is the fragment's depth within the range `0.0..=1.0`. Each fragment's contribution is
+/// further scaled by its alpha. This matches the implementation within the accumulation fragment
+/// shader.
+pub fn oit_weight(depth: f32) -> f32 {
+    let d = 1.0 - depth;
+    (3e3 * d * d * d).max(1e-2)
+}
+
+/// Composite the given translucent `fragments`, each described by a color and a depth, over the
+/// `dst` color using weighted blended order-independent transparency.
+///
+/// This mirrors the accumulation and resolve passes performed by the **Renderer** when using
+/// `OitMode::WeightedBlended` and is useful for predicting the result on the CPU. Other than for
+/// floating point error, the result does not depend on the order of the `fragments`.
+pub fn oit_composite(dst: LinSrgba, fragments: &[(LinSrgba, f32)]) -> LinSrgba {
+    let mut accum = [0.0; 4];
+    let mut revealage = 1.0;
+    for &(color, depth) in fragments {
+        let (r, g, b, a) = color.into();
+        let w = oit_weight(depth);
+        accum[0] += r * a * w;
+        accum[1] += g * a * w;
+        accum[2] += b * a * w;
+        accum[3] += a * w;
+        revealage *= 1.0 - a;
+    }
+    let total = accum[3].max(1e-5);
+    let alpha = 1.0 - revealage;
+    let (dr, dg, db, da) = dst.into();
+    let blend = |src: f32, dst: f32| src / total * alpha + dst * (1.0 - alpha);
+    LinSrgba::new(
+        blend(accum[0], dr),
+        blend(accum[1], dg),
+        blend(accum[2], db),
+        alpha + da * (1.0 - alpha),
+    )
+}
+
+/// Produce a signed distance field from the given row-major `coverage` values of a texture with
+/// the given size.
+///
+/// Values of at least `0.5` are considered inside of the shape. Each resulting value is the
+/// distance in pixels to the nearest edge, negative inside of the shape and positive outside,
+/// where edges lie halfway between neighbouring texels. The search for an edge is limited to
+/// `spread` pixels in each direction and the distances are clamped to `spread`. Texels beyond the
+/// edges of the texture are considered outside of the shape.
+///
+/// This is a brute force search matching the fragment shader used by `Renderer::render_sdf` when
+/// `spread` is `Renderer::SDF_SPREAD`, and is useful for producing distance fields on the CPU.
+///
+/// **Panic!**s if the length of `coverage` does not match the size.
+pub fn signed_distance_field(coverage: &[f32], [w, h]: [u32; 2], spread: u32) -> Vec<f32> {
+    assert_eq!(
+        coverage.len(),
+        w as usize * h as usize,
+        "the coverage length must match the size",
+    );
+    let is_inside = |x: i64, y: i64| {
+        if x < 0 || y < 0 || x >= w as i64 || y >= h as i64 {
+            return false;
+        }
+        coverage[y as usize * w as usize + x as usize] >= 0.5
+    };
+    let spread = spread as i64;
+    let mut sdf = Vec::with_capacity(coverage.len());
+    for y in 0..h as i64 {
+        for x in 0..w as i64 {
+            let inside = is_inside(x, y);
+            let mut nearest = spread as f32 + 0.5;
+            for dy in -spread..=spread {
+                for dx in -spread..=spread {
+                    if is_inside(x + dx, y + dy) != inside {
+                        let d = ((dx * dx + dy * dy) as f32).sqrt();
+                        nearest = nearest.min(d);
+                    }
+                }
+            }
+            let distance = (nearest - 0.5).min(spread as f32);
+            sdf.push(if inside { -distance } else { distance });
+        }
+    }
+    sdf
+}
+
+/// The depth at which background gradients are drawn, behind all other geometry.
+///
+/// Background gradients are only visible when the depth compare function passes for fragments at
+/// the cleared depth of `1.0`, as is the case with the default `LessEqual`.
+pub const BACKGROUND_GRADIENT_DEPTH: f32 = 1.0;
+
+/// Produce the vertices of the triangles that fill an output attachment with the given background
+/// gradient.
+///
+/// The vertices are positioned in normalised device coordinates at `BACKGROUND_GRADIENT_DEPTH`.
+pub fn gradient_vertices(gradient: &draw::background::Gradient) -> Vec<Vertex> {
+    gradient
+        .triangles()
+        .into_iter()
+        .map(|([x, y], color)| {
+            // In wgpu, *y* increases in the downwards direction, so we negate it.
+            let position = [x, -y, BACKGROUND_GRADIENT_DEPTH];
+            let (r, g, b, a) = color.into();
+            let color = [r, g, b, a];
+            let tex_coords = [0.0, 0.0];
+            Vertex {
+                position,
+                color,
+                tex_coords,
+            }
+        })
+        .collect()
+}
+
+/// Map a point in the logical coordinate space of a **Draw** to the physical pixel of an output
+/// attachment with the given size in pixels.
+///
+/// The origin of the logical space is the centre of the attachment and *y* increases upwards,
+/// whereas the returned pixel coordinates start from the top-left. Returns `None` if the point
+/// lies outside of the attachment.
+pub fn logical_to_physical_pixel(
+    point: geom::Point2<f32>,
+    [w, h]: [u32; 2],
+    scale_factor: f32,
+) -> Option<[u32; 2]> {
+    let x = (point.x * scale_factor + w as f32 * 0.5).floor();
+    let y = (h as f32 * 0.5 - point.y * scale_factor).floor();
+    if x < 0.0 || y < 0.0 || x >= w as f32 || y >= h as f32 {
+        return None;
+    }
+    Some([x as u32, y as u32])
+}
+
+/// Project a point in the coordinate space of a **Draw** to the physical pixel coordinates of an
+/// output attachment with the given size in pixels.
+///
+/// This applies the same mapping to normalised device coordinates as `Vertex::from_mesh_vertex`
+/// followed by the viewport transform. The returned coordinates start from the top-left of the
+/// attachment, so the origin of the **Draw** maps to the centre. Unlike
+/// `logical_to_physical_pixel`, points beyond the left, right, top or bottom edges are still
+/// returned, which is useful for positioning overlays that are partially off-screen.
+///
+/// Returns `None` if the point lies in front of the near plane or beyond the far plane. Such
+/// points are clipped when rendered.
+pub fn world_to_screen(
+    point: geom::Point3<f32>,
+    [w, h]: [u32; 2],
+    scale_factor: f32,
+    handedness: Handedness,
+) -> Option<geom::Point2<f32>> {
+    let color = draw::mesh::vertex::Color::new(0.0, 0.0, 0.0, 0.0);
+    let tex_coords = draw::mesh::vertex::default_tex_coords();
+    let v = draw::mesh::vertex::new(point, color, tex_coords);
+    let vertex = Vertex::from_mesh_vertex(v, w as _, h as _, scale_factor);
+    let [x, y, z] = vertex.position;
+    let z = z * handedness.z_sign();
+    if z < 0.0 || z > 1.0 {
+        return None;
+    }
+    let x = (x + 1.0) * 0.5 * w as f32;
+    let y = (y + 1.0) * 0.5 * h as f32;
+    Some(geom::pt2(x, y))
+}
+
+// Decode the bytes of a single pixel of the given format as a linear color.
+fn decode_pixel(format: wgpu::TextureFormat, bytes: &[u8]) -> Option<LinSrgba> {
+    let unorm8 = |i: usize| bytes[i] as f32 / 255.0;
+    let color = match format {
+        wgpu::TextureFormat::Rgba8Unorm => {
+            LinSrgba::new(unorm8(0), unorm8(1), unorm8(2), unorm8(3))
+        }
+        wgpu::TextureFormat::Bgra8Unorm => {
+            LinSrgba::new(unorm8(2), unorm8(1), unorm8(0), unorm8(3))
+        }
+        wgpu::TextureFormat::Rgba8UnormSrgb => {
+            color::Srgba::new(unorm8(0), unorm8(1), unorm8(2), unorm8(3)).into_linear()
+        }
+        wgpu::TextureFormat::Bgra8UnormSrgb => {
+            color::Srgba::new(unorm8(2), unorm8(1), unorm8(0), unorm8(3)).into_linear()
+        }
+        wgpu::TextureFormat::Rgba16Unorm => {
+            let unorm16 = |i: usize| {
+                let bits = [bytes[i * 2], bytes[i * 2 + 1]];
+                u16::from_ne_bytes(bits) as f32 / 65535.0
+            };
+            LinSrgba::new(unorm16(0), unorm16(1), unorm16(2), unorm16(3))
+        }
+        _ => return None,
+    };
+    Some(color)
+}
+
+// Snap the given normalised device coordinate to the centre of the nearest pixel along an axis
+// with the given length in pixels.
+fn snap_to_pixel_center(ndc: f32, len_px: u32) -> f32 {
+    let len_px = len_px as f32;
+    let px = (ndc + 1.0) * 0.5 * len_px;
+    let snapped_px = px.floor() + 0.5;
+    snapped_px / len_px * 2.0 - 1.0
+}
+
+// Whether or not the given format may be used for the renderer's depth texture.
+fn is_depth_format(format: wgpu::TextureFormat) -> bool {
+    match format {
+        wgpu::TextureFormat::Depth32Float
+        | wgpu::TextureFormat::Depth24Plus
+        | wgpu::TextureFormat::Depth24PlusStencil8 => true,
+        _ => false,
+    }
+}
+
+// The usage of the renderer's depth texture.
+fn depth_usage(copyable: bool) -> wgpu::TextureUsage {
+    match copyable {
+        true => wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+        false => wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+    }
+}
+
+// Create a buffer with room for `capacity` elements of type `T` that may be uploaded to via
+// `upload_to_buffer`.
+fn create_buffer<T>(
+    device: &wgpu::Device,
+    capacity: usize,
+    usage: wgpu::BufferUsage,
+) -> wgpu::Buffer {
+    let size = (capacity * std::mem::size_of::<T>()) as wgpu::BufferAddress;
+    let usage = usage | wgpu::BufferUsage::COPY_DST;
+    device.create_buffer(&wgpu::BufferDescriptor { size, usage })
+}
+
+// Encode a copy of the given data to the start of the given buffer via the staging buffer.
+//
+// If the data exceeds the buffer's capacity, the buffer is first replaced with one whose capacity
+// is the next power of two.
+//
+// The data is written to the mapped staging buffer on the current thread. It is moved into the
+// mapping callback and returned once written, so no intermediary copy is made.
+fn upload_to_buffer<T>(
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    staging_buffer: &mut StagingBuffer,
+    buffer: &mut wgpu::Buffer,
+    capacity: &mut usize,
+    data: &mut Vec<T>,
+    usage: wgpu::BufferUsage,
+) where
+    T: 'static + Copy,
+{
+    if data.len() > *capacity {
+        *capacity = data.len().next_power_of_two();
+        *buffer = create_buffer::<T>(device, *capacity, usage);
+    }
+    if data.is_empty() {
+        return;
+    }
+    let size = (data.len() * std::mem::size_of::<T>()) as wgpu::BufferAddress;
+    let offset = staging_buffer.allocate(device, size);
+    let written = std::rc::Rc::new(std::cell::RefCell::new(None));
+    let written2 = written.clone();
+    let src = std::mem::replace(data, vec![]);
+    staging_buffer.buffer.map_write_async(
+        offset,
+        size,
+        move |result: wgpu::BufferMapAsyncResult<&mut [T]>| {
+            let mapping = result.expect("failed to map staging buffer");
+            mapping.data.copy_from_slice(&src[..]);
+            *written2.borrow_mut() = Some(src);
+        },
+    );
+    device.poll(true);
+    *data = written
+        .borrow_mut()
+        .take()
+        .expect("staging buffer was not written");
+    encoder.copy_buffer_to_buffer(&staging_buffer.buffer, offset, buffer, 0, size);
+}
+
 fn create_depth_texture(
     device: &wgpu::Device,
     size: [u32; 2],
     depth_format: wgpu::TextureFormat,
     sample_count: u32,
+    usage: wgpu::TextureUsage,
 ) -> wgpu::Texture {
     wgpu::TextureBuilder::new()
         .size(size)
         .format(depth_format)
-        .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT)
+        .usage(usage)
         .sample_count(sample_count)
         .build(device)
 }
@@ -360,6 +2365,292 @@ fn bind_group(device: &wgpu::Device, layout: &wgpu::BindGroupLayout) -> wgpu::Bi
     wgpu::BindGroupBuilder::new().build(device, layout)
 }
 
+fn linear_depth_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    wgpu::BindGroupLayoutBuilder::new()
+        .uniform_buffer(wgpu::ShaderStage::FRAGMENT, false)
+        .build(device)
+}
+
+fn linear_depth_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    uniform_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    wgpu::BindGroupBuilder::new()
+        .buffer::<LinearDepthUniforms>(uniform_buffer, 0..1)
+        .build(device, layout)
+}
+
+fn linear_depth_render_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    vs_mod: &wgpu::ShaderModule,
+    fs_mod: &wgpu::ShaderModule,
+    dst_format: wgpu::TextureFormat,
+    depth_format: wgpu::TextureFormat,
+    depth_compare: wgpu::CompareFunction,
+    msaa_samples: u32,
+    sample_mask: u32,
+    blend_mode: BlendMode,
+) -> wgpu::RenderPipeline {
+    let color = blend_mode.color_state(dst_format);
+    let linear_depth = wgpu::ColorStateDescriptor {
+        format: Renderer::LINEAR_DEPTH_FORMAT,
+        color_blend: wgpu::BlendDescriptor::REPLACE,
+        alpha_blend: wgpu::BlendDescriptor::REPLACE,
+        write_mask: wgpu::ColorWrite::ALL,
+    };
+    let targets = [color, linear_depth];
+    wgpu::RenderPipelineBuilder::from_layout_descriptor(&[layout][..], vs_mod)
+        .fragment_shader(fs_mod)
+        .color_states(&targets)
+        .add_vertex_buffer::<Vertex>()
+        .depth_format(depth_format)
+        .depth_compare(depth_compare)
+        .sample_count(msaa_samples)
+        .sample_mask(sample_mask)
+        .build(device)
+}
+
+fn create_oit(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    vs_mod: &wgpu::ShaderModule,
+    color_format: wgpu::TextureFormat,
+    depth_format: wgpu::TextureFormat,
+    depth_compare: wgpu::CompareFunction,
+) -> Oit {
+    let fs = include_bytes!("shaders/oit_frag.spv");
+    let fs_spirv =
+        wgpu::read_spirv(std::io::Cursor::new(&fs[..])).expect("failed to read hard-coded SPIRV");
+    let fs_mod = device.create_shader_module(&fs_spirv);
+    let resolve_fs = include_bytes!("shaders/oit_resolve_frag.spv");
+    let resolve_fs_spirv = wgpu::read_spirv(std::io::Cursor::new(&resolve_fs[..]))
+        .expect("failed to read hard-coded SPIRV");
+    let resolve_fs_mod = device.create_shader_module(&resolve_fs_spirv);
+
+    // Weighted colors are summed while revealage is multiplied by the transparency of each
+    // fragment.
+    let additive_blend = || wgpu::BlendDescriptor {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let revealage_blend = || wgpu::BlendDescriptor {
+        src_factor: wgpu::BlendFactor::Zero,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcColor,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let accum = wgpu::ColorStateDescriptor {
+        format: Renderer::OIT_ACCUM_FORMAT,
+        color_blend: additive_blend(),
+        alpha_blend: additive_blend(),
+        write_mask: wgpu::ColorWrite::ALL,
+    };
+    let revealage = wgpu::ColorStateDescriptor {
+        format: Renderer::OIT_REVEALAGE_FORMAT,
+        color_blend: revealage_blend(),
+        alpha_blend: revealage_blend(),
+        write_mask: wgpu::ColorWrite::ALL,
+    };
+    let targets = [accum, revealage];
+    let accum_pipeline = wgpu::RenderPipelineBuilder::from_layout_descriptor(&[layout][..], vs_mod)
+        .fragment_shader(&fs_mod)
+        .color_states(&targets)
+        .add_vertex_buffer::<Vertex>()
+        .depth_format(depth_format)
+        .depth_write_enabled(false)
+        .depth_compare(depth_compare)
+        .build(device);
+
+    // The resolve pass samples both targets and blends the result over the output attachment.
+    let resolve_bind_group_layout = wgpu::BindGroupLayoutBuilder::new()
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+        )
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+        )
+        .sampler(wgpu::ShaderStage::FRAGMENT)
+        .build(device);
+    let resolve_pipeline = wgpu::RenderPipelineBuilder::from_layout_descriptor(
+        &[&resolve_bind_group_layout][..],
+        vs_mod,
+    )
+    .fragment_shader(&resolve_fs_mod)
+    .color_format(color_format)
+    .add_vertex_buffer::<Vertex>()
+    .primitive_topology(wgpu::PrimitiveTopology::TriangleStrip)
+    .build(device);
+
+    let sampler = wgpu::SamplerBuilder::new().build(device);
+    let quad_vertex_buffer = device
+        .create_buffer_mapped(QUAD_VERTICES.len(), wgpu::BufferUsage::VERTEX)
+        .fill_from_slice(&QUAD_VERTICES[..]);
+    Oit {
+        depth_format,
+        depth_compare,
+        _fs_mod: fs_mod,
+        _resolve_fs_mod: resolve_fs_mod,
+        accum_pipeline,
+        resolve_bind_group_layout,
+        resolve_pipeline,
+        sampler,
+        quad_vertex_buffer,
+        targets: None,
+    }
+}
+
+fn create_oit_targets(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    size: [u32; 2],
+) -> OitTargets {
+    let usage = wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED;
+    let accum = wgpu::TextureBuilder::new()
+        .size(size)
+        .format(Renderer::OIT_ACCUM_FORMAT)
+        .usage(usage)
+        .build(device);
+    let revealage = wgpu::TextureBuilder::new()
+        .size(size)
+        .format(Renderer::OIT_REVEALAGE_FORMAT)
+        .usage(usage)
+        .build(device);
+    let accum_view = accum.create_default_view();
+    let revealage_view = revealage.create_default_view();
+    let resolve_bind_group = wgpu::BindGroupBuilder::new()
+        .texture_view(&accum_view)
+        .texture_view(&revealage_view)
+        .sampler(sampler)
+        .build(device, layout);
+    OitTargets {
+        accum,
+        accum_view,
+        _revealage: revealage,
+        revealage_view,
+        resolve_bind_group,
+    }
+}
+
+// A full-screen quad, drawn as a triangle strip.
+const QUAD_VERTICES: [Vertex; 4] = [
+    Vertex {
+        position: [-1.0, -1.0, 0.0],
+        color: [1.0; 4],
+        tex_coords: [0.0; 2],
+    },
+    Vertex {
+        position: [-1.0, 1.0, 0.0],
+        color: [1.0; 4],
+        tex_coords: [0.0; 2],
+    },
+    Vertex {
+        position: [1.0, -1.0, 0.0],
+        color: [1.0; 4],
+        tex_coords: [0.0; 2],
+    },
+    Vertex {
+        position: [1.0, 1.0, 0.0],
+        color: [1.0; 4],
+        tex_coords: [0.0; 2],
+    },
+];
+
+fn create_accumulator(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    vs_mod: &wgpu::ShaderModule,
+    fs_mod: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    depth_format: wgpu::TextureFormat,
+    depth_compare: wgpu::CompareFunction,
+    msaa_samples: u32,
+    sample_mask: u32,
+) -> Accumulator {
+    // Scales the destination by the blend color, ignoring the fragment color.
+    let decay_blend = || wgpu::BlendDescriptor {
+        src_factor: wgpu::BlendFactor::Zero,
+        dst_factor: wgpu::BlendFactor::BlendColor,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let decay_pipeline = wgpu::RenderPipelineBuilder::from_layout_descriptor(&[layout][..], vs_mod)
+        .fragment_shader(fs_mod)
+        .color_format(format)
+        .color_blend(decay_blend())
+        .alpha_blend(decay_blend())
+        .add_vertex_buffer::<Vertex>()
+        .primitive_topology(wgpu::PrimitiveTopology::TriangleStrip)
+        .sample_count(msaa_samples)
+        .build(device);
+    let additive_blend = || wgpu::BlendDescriptor {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let additive_pipeline =
+        wgpu::RenderPipelineBuilder::from_layout_descriptor(&[layout][..], vs_mod)
+            .fragment_shader(fs_mod)
+            .color_format(format)
+            .color_blend(additive_blend())
+            .alpha_blend(additive_blend())
+            .add_vertex_buffer::<Vertex>()
+            .depth_format(depth_format)
+            .depth_compare(depth_compare)
+            .sample_count(msaa_samples)
+            .sample_mask(sample_mask)
+            .build(device);
+    let quad_vertex_buffer = device
+        .create_buffer_mapped(QUAD_VERTICES.len(), wgpu::BufferUsage::VERTEX)
+        .fill_from_slice(&QUAD_VERTICES[..]);
+    Accumulator {
+        format,
+        decay_pipeline,
+        additive_pipeline,
+        quad_vertex_buffer,
+    }
+}
+
+fn create_sdf(device: &wgpu::Device, vs_mod: &wgpu::ShaderModule) -> Sdf {
+    let fs = include_bytes!("shaders/sdf_frag.spv");
+    let fs_spirv =
+        wgpu::read_spirv(std::io::Cursor::new(&fs[..])).expect("failed to read hard-coded SPIRV");
+    let fs_mod = device.create_shader_module(&fs_spirv);
+    let bind_group_layout = wgpu::BindGroupLayoutBuilder::new()
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+        )
+        .sampler(wgpu::ShaderStage::FRAGMENT)
+        .build(device);
+    let pipeline =
+        wgpu::RenderPipelineBuilder::from_layout_descriptor(&[&bind_group_layout][..], vs_mod)
+            .fragment_shader(&fs_mod)
+            .color_format(Renderer::SDF_FORMAT)
+            .color_blend(wgpu::BlendDescriptor::REPLACE)
+            .alpha_blend(wgpu::BlendDescriptor::REPLACE)
+            .add_vertex_buffer::<Vertex>()
+            .primitive_topology(wgpu::PrimitiveTopology::TriangleStrip)
+            .build(device);
+    let sampler = wgpu::SamplerBuilder::new().build(device);
+    let quad_vertex_buffer = device
+        .create_buffer_mapped(QUAD_VERTICES.len(), wgpu::BufferUsage::VERTEX)
+        .fill_from_slice(&QUAD_VERTICES[..]);
+    Sdf {
+        _fs_mod: fs_mod,
+        bind_group_layout,
+        pipeline,
+        sampler,
+        quad_vertex_buffer,
+    }
+}
+
 fn render_pipeline(
     device: &wgpu::Device,
     layout: &wgpu::BindGroupLayout,
@@ -367,11 +2658,46 @@ fn render_pipeline(
     fs_mod: &wgpu::ShaderModule,
     dst_format: wgpu::TextureFormat,
     depth_format: wgpu::TextureFormat,
+    depth_compare: wgpu::CompareFunction,
+    msaa_samples: u32,
+    sample_mask: u32,
+    blend_mode: BlendMode,
+) -> wgpu::RenderPipeline {
+    wgpu::RenderPipelineBuilder::from_layout_descriptor(&[layout][..], vs_mod)
+        .fragment_shader(fs_mod)
+        .color_state(blend_mode.color_state(dst_format))
+        .add_vertex_buffer::<Vertex>()
+        .depth_format(depth_format)
+        .depth_compare(depth_compare)
+        .sample_count(msaa_samples)
+        .sample_mask(sample_mask)
+        .build(device)
+}
+
+/// Create a render pipeline compatible with the draw **Renderer**'s vertex layout that outputs to
+/// multiple color targets.
+///
+/// Each of the given `targets` describes the format and blend state of the color attachment at
+/// the same index. This is useful for effects that write to several targets within a single pass,
+/// e.g. a scene target alongside a bloom threshold target. The fragment shader must declare an
+/// output for each target.
+///
+/// Writes to individual targets may be disabled by specifying a `write_mask` of
+/// `wgpu::ColorWrite::empty()` within the target's descriptor, e.g. to write color to attachment
+/// `0` while leaving attachment `1` untouched.
+pub fn render_pipeline_mrt(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    vs_mod: &wgpu::ShaderModule,
+    fs_mod: &wgpu::ShaderModule,
+    targets: &[wgpu::ColorStateDescriptor],
+    depth_format: wgpu::TextureFormat,
     msaa_samples: u32,
 ) -> wgpu::RenderPipeline {
+    assert!(!targets.is_empty(), "at least one color target is required");
     wgpu::RenderPipelineBuilder::from_layout_descriptor(&[layout][..], vs_mod)
         .fragment_shader(fs_mod)
-        .color_format(dst_format)
+        .color_states(targets)
         .add_vertex_buffer::<Vertex>()
         .depth_format(depth_format)
         .sample_count(msaa_samples)
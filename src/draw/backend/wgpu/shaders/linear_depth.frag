@@ -0,0 +1,24 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `linear_depth_frag.spv`. You can do so using `glslangValidator`
+// with the following command:
+// `glslangValidator -V -o linear_depth_frag.spv linear_depth.frag`
+
+#version 450
+
+layout(location = 0) in vec4 v_color;
+layout(location = 1) in vec2 v_tex_coords;
+
+layout(location = 0) out vec4 f_color;
+layout(location = 1) out float f_linear_depth;
+
+layout(set = 0, binding = 0) uniform Data {
+    float depth_scale;
+} uniforms;
+
+void main() {
+    f_color = v_color;
+    // Map the fragment depth back to the *z* coordinate of the **Draw**'s coordinate space.
+    f_linear_depth = gl_FragCoord.z * uniforms.depth_scale;
+}
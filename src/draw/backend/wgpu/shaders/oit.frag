@@ -0,0 +1,22 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `oit_frag.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o oit_frag.spv oit.frag`
+
+#version 450
+
+layout(location = 0) in vec4 v_color;
+layout(location = 1) in vec2 v_tex_coords;
+
+layout(location = 0) out vec4 f_accum;
+layout(location = 1) out vec4 f_revealage;
+
+void main() {
+    // A depth weight function from McGuire and Bavoil's "Weighted Blended Order-Independent
+    // Transparency", favouring fragments that are closer to the viewer.
+    float a = v_color.a;
+    float w = max(1e-2, 3e3 * pow(1.0 - gl_FragCoord.z, 3.0));
+    f_accum = vec4(v_color.rgb * a, a) * w;
+    f_revealage = vec4(a);
+}
@@ -0,0 +1,26 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `oit_resolve_frag.spv`. You can do so using `glslangValidator`
+// with the following command:
+// `glslangValidator -V -o oit_resolve_frag.spv oit_resolve.frag`
+
+#version 450
+
+layout(location = 0) in vec4 v_color;
+layout(location = 1) in vec2 v_tex_coords;
+
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D t_accum;
+layout(set = 0, binding = 1) uniform texture2D t_revealage;
+layout(set = 0, binding = 2) uniform sampler t_sampler;
+
+void main() {
+    ivec2 coords = ivec2(gl_FragCoord.xy);
+    vec4 accum = texelFetch(sampler2D(t_accum, t_sampler), coords, 0);
+    float revealage = texelFetch(sampler2D(t_revealage, t_sampler), coords, 0).r;
+    // Composite the weighted average color over the destination by the total coverage.
+    vec3 average = accum.rgb / max(accum.a, 1e-5);
+    f_color = vec4(average, 1.0 - revealage);
+}
@@ -0,0 +1,45 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `sdf_frag.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o sdf_frag.spv sdf.frag`
+
+#version 450
+
+layout(location = 0) in vec4 v_color;
+layout(location = 1) in vec2 v_tex_coords;
+
+layout(location = 0) out float f_distance;
+
+layout(set = 0, binding = 0) uniform texture2D t_coverage;
+layout(set = 0, binding = 1) uniform sampler t_sampler;
+
+// Matches `Renderer::SDF_SPREAD`.
+const int SPREAD = 8;
+
+// Texels beyond the edges of the coverage texture are considered outside of the shape.
+bool is_inside(ivec2 coords, ivec2 size) {
+    if (coords.x < 0 || coords.y < 0 || coords.x >= size.x || coords.y >= size.y) {
+        return false;
+    }
+    return texelFetch(sampler2D(t_coverage, t_sampler), coords, 0).a >= 0.5;
+}
+
+// Matches `signed_distance_field`.
+void main() {
+    ivec2 size = textureSize(sampler2D(t_coverage, t_sampler), 0);
+    ivec2 coords = ivec2(gl_FragCoord.xy);
+    bool inside = is_inside(coords, size);
+    // Find the distance to the nearest texel on the other side of the edge.
+    float nearest = float(SPREAD) + 0.5;
+    for (int y = -SPREAD; y <= SPREAD; y++) {
+        for (int x = -SPREAD; x <= SPREAD; x++) {
+            if (is_inside(coords + ivec2(x, y), size) != inside) {
+                nearest = min(nearest, length(vec2(float(x), float(y))));
+            }
+        }
+    }
+    // The edge lies halfway between the texels, so offset the distance by half a texel.
+    float distance = min(nearest - 0.5, float(SPREAD));
+    f_distance = inside ? -distance : distance;
+}
@@ -1,5 +1,5 @@
 use crate::color::{self, IntoLinSrgba, Srgb, Srgba};
-use crate::draw::properties::ColorScalar;
+use crate::draw::properties::{ColorScalar, LinSrgba};
 use crate::draw::Draw;
 use crate::geom;
 use crate::math::BaseFloat;
@@ -12,6 +12,16 @@ where
     draw: &'a Draw<S>,
 }
 
+/// A gradient with which the background is filled in place of a flat clear color.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Gradient {
+    /// Linearly interpolates from the `top` edge of the frame to the `bottom` edge.
+    Linear { top: LinSrgba, bottom: LinSrgba },
+    /// Interpolates from the `inner` color at the centre of the frame to the `outer` color at the
+    /// corners of the frame.
+    Radial { inner: LinSrgba, outer: LinSrgba },
+}
+
 /// Begin coloring the background.
 pub fn new<'a, S>(draw: &'a Draw<S>) -> Background<'a, S>
 where
@@ -35,6 +45,43 @@ where
     {
         if let Ok(mut state) = self.draw.state.try_borrow_mut() {
             state.background_color = Some(color.into_lin_srgba());
+            state.background_gradient = None;
+        }
+        self
+    }
+
+    /// Fill the background with a vertical gradient from the `top` color to the `bottom` color.
+    ///
+    /// The frame is first cleared to transparent, so the alpha of the given colors is preserved,
+    /// e.g. when capturing frames. This replaces any previously specified background color.
+    pub fn gradient<C>(self, top: C, bottom: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let top = top.into_lin_srgba();
+        let bottom = bottom.into_lin_srgba();
+        self.set_gradient(Gradient::Linear { top, bottom })
+    }
+
+    /// Fill the background with a radial gradient from the `inner` color at the centre of the
+    /// frame to the `outer` color at its corners.
+    ///
+    /// The frame is first cleared to transparent, so the alpha of the given colors is preserved,
+    /// e.g. when capturing frames. This replaces any previously specified background color.
+    pub fn radial_gradient<C>(self, inner: C, outer: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let inner = inner.into_lin_srgba();
+        let outer = outer.into_lin_srgba();
+        self.set_gradient(Gradient::Radial { inner, outer })
+    }
+
+    // Store the gradient in place of the background color.
+    fn set_gradient(self, gradient: Gradient) -> Self {
+        if let Ok(mut state) = self.draw.state.try_borrow_mut() {
+            state.background_color = None;
+            state.background_gradient = Some(gradient);
         }
         self
     }
@@ -108,3 +155,72 @@ where
         self.color(color::Hsva::new(hue, s, v, a))
     }
 }
+
+impl Gradient {
+    /// The number of segments used to approximate the ring of a radial gradient.
+    pub const RADIAL_SEGMENTS: usize = 64;
+
+    /// The color of the gradient at the given normalised position within the frame.
+    ///
+    /// Both axes range from `-1.0` to `1.0`, where `[0.0, 0.0]` is the centre of the frame and
+    /// *y* increases upwards.
+    pub fn color_at(&self, [x, y]: [f32; 2]) -> LinSrgba {
+        match *self {
+            Gradient::Linear { top, bottom } => lerp(bottom, top, (y + 1.0) * 0.5),
+            Gradient::Radial { inner, outer } => {
+                let t = (x * x + y * y).sqrt() / std::f32::consts::SQRT_2;
+                lerp(inner, outer, t)
+            }
+        }
+    }
+
+    /// Produce a list of triangles covering the entire frame, described by their normalised
+    /// positions (see `color_at`) and colors.
+    ///
+    /// A linear gradient is described by a single quad. A radial gradient is described by a fan
+    /// of `RADIAL_SEGMENTS` triangles around the centre, whose outer ring passes through the
+    /// corners of the frame.
+    pub fn triangles(&self) -> Vec<([f32; 2], LinSrgba)> {
+        match *self {
+            Gradient::Linear { .. } => {
+                let tl = [-1.0, 1.0];
+                let tr = [1.0, 1.0];
+                let bl = [-1.0, -1.0];
+                let br = [1.0, -1.0];
+                [tl, bl, tr, tr, bl, br]
+                    .iter()
+                    .map(|&p| (p, self.color_at(p)))
+                    .collect()
+            }
+            Gradient::Radial { inner, outer } => {
+                let radius = std::f32::consts::SQRT_2;
+                let ring = |i: usize| {
+                    let turn = i as f32 / Self::RADIAL_SEGMENTS as f32;
+                    let rad = turn * 2.0 * std::f32::consts::PI;
+                    [rad.cos() * radius, rad.sin() * radius]
+                };
+                (0..Self::RADIAL_SEGMENTS)
+                    .flat_map(|i| {
+                        let centre = ([0.0, 0.0], inner);
+                        let a = (ring(i), outer);
+                        let b = (ring(i + 1), outer);
+                        vec![centre, a, b]
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+// Linearly interpolate between the colors `a` and `b` by the amount `t`, clamped to `0.0..=1.0`.
+fn lerp(a: LinSrgba, b: LinSrgba, t: f32) -> LinSrgba {
+    let t = t.max(0.0).min(1.0);
+    let (ar, ag, ab, aa) = a.into();
+    let (br, bg, bb, ba) = b.into();
+    LinSrgba::new(
+        ar + (br - ar) * t,
+        ag + (bg - ag) * t,
+        ab + (bb - ab) * t,
+        aa + (ba - aa) * t,
+    )
+}
@@ -55,6 +55,8 @@ pub struct DrawingContext<'a, S> {
     pub text_buffer: &'a mut String,
     /// Cache for text glyphs.
     pub glyph_cache: &'a mut draw::GlyphCache,
+    /// The default tolerance used when tessellating curves, if one was specified for the **Draw**.
+    pub tolerance: Option<f32>,
 }
 
 /// Construct a new **Drawing** instance.
@@ -95,6 +97,7 @@ impl<'a, S> DrawingContext<'a, S> {
             ref mut path_event_buffer,
             ref mut text_buffer,
             ref mut glyph_cache,
+            tolerance,
         } = *state;
         DrawingContext {
             mesh: intermediary_mesh,
@@ -102,6 +105,7 @@ impl<'a, S> DrawingContext<'a, S> {
             path_event_buffer: path_event_buffer,
             text_buffer: text_buffer,
             glyph_cache: glyph_cache,
+            tolerance: tolerance,
         }
     }
 }
@@ -140,6 +144,21 @@ where
         id
     }
 
+    /// Specify the z-order of the primitive, used to layer primitives without relying on depth.
+    ///
+    /// Primitives with a higher z-order are rendered after (on top of) those with a lower
+    /// z-order, regardless of the order in which they were drawn. Primitives with equal
+    /// z-orders are rendered in the order in which they were drawn. By default, all primitives
+    /// have a z-order of `0`.
+    pub fn z_order(self, z_order: i32) -> Self {
+        self.draw
+            .state
+            .borrow_mut()
+            .z_orders
+            .insert(self.index, z_order);
+        self
+    }
+
     // Map the given function onto the primitive stored within **Draw** at `index`.
     //
     // The functionn is only applied if the node has not yet been **Drawn**.
@@ -1529,6 +1548,93 @@ where
     }
 }
 
+// Stamping methods.
+
+impl<'a, T, S> Drawing<'a, T, S>
+where
+    T: SetPosition<S> + SetOrientation<S> + Clone + Into<Primitive<S>>,
+    Primitive<S>: Into<Option<T>>,
+    S: BaseFloat,
+{
+    /// Stamp copies of the shape at evenly spaced intervals along the given path.
+    ///
+    /// The drawing itself is positioned at the start of the path, while each copy is positioned
+    /// `spacing` further along the path than the last. Any previously specified position is
+    /// overridden. If `spacing` is not positive, only the drawing itself is placed.
+    ///
+    /// Copies are made of the shape's current state, so properties should be specified prior to
+    /// calling this method.
+    pub fn stamp_along(self, path: &[Point2<S>], spacing: S) -> Self {
+        self.stamp_along_inner(path, spacing, false)
+    }
+
+    /// The same as `stamp_along`, but each copy is also rotated around the *z* axis to follow the
+    /// tangent of the path at its position.
+    pub fn stamp_along_oriented(self, path: &[Point2<S>], spacing: S) -> Self {
+        self.stamp_along_inner(path, spacing, true)
+    }
+
+    /// Repeat the shape `count` times around the origin, e.g. for kaleidoscope and mandala
+    /// patterns.
+    ///
+    /// The drawing itself is the first copy, while each subsequent copy is rotated a further
+    /// `2π / count` radians counter-clockwise about the origin. Both the position and the *z*
+    /// orientation of each copy are rotated. Positions described relative to other nodes and
+    /// orientations described via `look_at` are left unchanged. A `count` of `0` or `1` leaves the
+    /// drawing unchanged.
+    ///
+    /// Copies are made of the shape's current state, so properties should be specified prior to
+    /// calling this method.
+    pub fn radial_repeat(self, count: u32) -> Self {
+        if count <= 1 {
+            return self;
+        }
+        let mut shape = None;
+        let drawing = self.map_ty(|ty| {
+            shape = Some(ty.clone());
+            ty
+        });
+        if let Some(shape) = shape {
+            let turn = S::from(2.0 * std::f64::consts::PI).unwrap();
+            let step = turn / S::from(count).unwrap();
+            for i in 1..count {
+                let angle = step * S::from(i).unwrap();
+                drawing.draw.a(rotate_about_origin(shape.clone(), angle));
+            }
+        }
+        drawing
+    }
+
+    // Shared between the `stamp_along` methods.
+    fn stamp_along_inner(self, path: &[Point2<S>], spacing: S, oriented: bool) -> Self {
+        let place = |ty: T, (p, angle): (Point2<S>, S)| {
+            let ty = SetPosition::x_y(ty, p.x, p.y);
+            // Positive orientations rotate clockwise, so negate the counter-clockwise tangent.
+            match oriented {
+                true => SetOrientation::z_radians(ty, -angle),
+                false => ty,
+            }
+        };
+        let mut stamps = stamp_points(path, spacing).into_iter();
+        let first = match stamps.next() {
+            None => return self,
+            Some(first) => first,
+        };
+        let mut shape = None;
+        let drawing = self.map_ty(|ty| {
+            let ty = place(ty, first);
+            shape = Some(ty.clone());
+            ty
+        });
+        if let Some(shape) = shape {
+            for stamp in stamps {
+                drawing.draw.a(place(shape.clone(), stamp));
+            }
+        }
+        drawing
+    }
+}
+
 // SetFill methods
 
 impl<'a, T, S> Drawing<'a, T, S>
@@ -1709,3 +1815,81 @@ where
         self.map_ty(|ty| ty.stroke_opts(opts))
     }
 }
+
+// Rotate the absolute position and the *z* orientation of the given shape counter-clockwise about
+// the origin by the given angle in radians.
+fn rotate_about_origin<T, S>(mut ty: T, angle: S) -> T
+where
+    T: SetPosition<S> + SetOrientation<S>,
+    S: BaseFloat,
+{
+    let absolute = |p: Option<position::Position<S>>| match p {
+        None => Some(S::zero()),
+        Some(position::Position::Absolute(s)) => Some(s),
+        Some(position::Position::Relative(..)) => None,
+    };
+    let pos = *SetPosition::properties(&mut ty);
+    if let (Some(x), Some(y)) = (absolute(pos.x), absolute(pos.y)) {
+        let (sin, cos) = angle.sin_cos();
+        ty = SetPosition::x_y(ty, x * cos - y * sin, x * sin + y * cos);
+    }
+    // Positive orientations rotate clockwise, so the angle is subtracted.
+    if let orientation::Properties::Axes(ref mut axes) = *SetOrientation::properties(&mut ty) {
+        axes.z = Some(match axes.z {
+            None => orientation::Orientation::Absolute(-angle),
+            Some(orientation::Orientation::Absolute(z)) => {
+                orientation::Orientation::Absolute(z - angle)
+            }
+            Some(orientation::Orientation::Relative(z, parent)) => {
+                orientation::Orientation::Relative(z - angle, parent)
+            }
+        });
+    }
+    ty
+}
+
+// Produce the positions and tangent angles at evenly spaced intervals along the given path.
+fn stamp_points<S>(path: &[Point2<S>], spacing: S) -> Vec<(Point2<S>, S)>
+where
+    S: BaseFloat,
+{
+    let mut stamps = vec![];
+    let first = match path.first() {
+        None => return stamps,
+        Some(&first) => first,
+    };
+    let segments = path
+        .windows(2)
+        .map(|w| (w[0], w[1]))
+        .filter(|&(a, b)| a != b);
+    let mut segments = segments.peekable();
+    let first_angle = segments
+        .peek()
+        .map(|&(a, b)| (b.y - a.y).atan2(b.x - a.x))
+        .unwrap_or_else(S::zero);
+    stamps.push((first, first_angle));
+    if spacing <= S::zero() {
+        return stamps;
+    }
+    let epsilon = spacing * S::from(1e-6).unwrap();
+    let mut count = S::one();
+    let mut start_len = S::zero();
+    for (a, b) in segments {
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let len = (dx * dx + dy * dy).sqrt();
+        let angle = dy.atan2(dx);
+        let mut target = count * spacing;
+        while target <= start_len + len + epsilon {
+            let t = ((target - start_len) / len).min(S::one());
+            let p = Point2 {
+                x: a.x + dx * t,
+                y: a.y + dy * t,
+            };
+            stamps.push((p, angle));
+            count = count + S::one();
+            target = count * spacing;
+        }
+        start_len = start_len + len;
+    }
+    stamps
+}
@@ -1,14 +1,17 @@
 //! A simple API for drawing 2D and 3D graphics. See the [**Draw** type](./struct.Draw.html) for
 //! more details.
 
+use crate::color;
 use crate::geom::graph::{edge, node};
 use crate::geom::{self, Vector3};
-use crate::math::BaseFloat;
+use crate::math::{BaseFloat, NumCast};
 use crate::text;
 use lyon::path::PathEvent;
 use lyon::tessellation::FillTessellator;
 use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
 use std::{fmt, mem, ops};
 
 pub use self::backend::wgpu::Renderer;
@@ -88,6 +91,8 @@ where
     mesh: Mesh<S>,
     /// The map from node indices to their vertex and index ranges within the mesh.
     ranges: HashMap<node::Index, Ranges>,
+    /// The z-order of each node for which one was specified, used to layer primitives.
+    z_orders: HashMap<node::Index, i32>,
     /// Primitives that are in the process of being drawn.
     drawing: HashMap<node::Index, Primitive<S>>,
     /// The last node that was **Drawn**.
@@ -96,6 +101,8 @@ where
     theme: Theme,
     /// If `Some`, the **Draw** should first clear the frame's gl context with the given color.
     background_color: Option<properties::LinSrgba>,
+    /// If `Some`, the **Draw** should first fill the frame with the given gradient.
+    background_gradient: Option<background::Gradient>,
 }
 
 /// The CPU half of the glyph cache used for caching text.
@@ -122,6 +129,8 @@ pub struct IntermediaryState<S> {
     text_buffer: String,
     /// The CPU side of the glyph cache.
     glyph_cache: GlyphCache,
+    /// The default tolerance used when tessellating curves, if one was specified.
+    tolerance: Option<f32>,
 }
 
 // Simple wrapper providing Clone and Debug.
@@ -181,6 +190,21 @@ where
     node_vertices: Option<RawNodeVertices<'a, S>>,
 }
 
+/// A snapshot of the tessellated geometry and background of a **Draw**, produced by
+/// `Draw::record`.
+///
+/// The recorded vertices are described in the same resolution-independent coordinates as the
+/// **Draw** itself. They are only mapped to pixels when rendered, allowing the same commands to be
+/// replayed via `Renderer::render_commands` at any number of output sizes without re-running the
+/// drawing code.
+#[derive(Clone, Debug)]
+pub struct DrawCommands<S = geom::scalar::Default> {
+    vertices: Vec<mesh::Vertex<S>>,
+    indices: Vec<usize>,
+    background_color: Option<properties::LinSrgba>,
+    background_gradient: Option<background::Gradient>,
+}
+
 impl GlyphCache {
     pub const DEFAULT_W: u32 = 256;
     pub const DEFAULT_H: u32 = 256;
@@ -222,9 +246,11 @@ where
         self.geom_graph_dfs.borrow_mut().reset(&self.geom_graph);
         self.drawing.clear();
         self.ranges.clear();
+        self.z_orders.clear();
         self.intermediary_state.borrow_mut().reset();
         self.mesh.clear();
         self.background_color = None;
+        self.background_gradient = None;
         self.last_node_drawn = None;
     }
 
@@ -337,9 +363,22 @@ where
         self.state.borrow_mut().reset();
     }
 
+    /// Specify the default tolerance used when tessellating curves for all primitives drawn with
+    /// this **Draw** instance.
+    ///
+    /// The tolerance is the maximum allowed distance between a curve and its approximation. Lower
+    /// values produce smoother curves at the cost of more triangles. Primitives that specify their
+    /// own tolerance take precedence.
+    ///
+    /// Unlike the background color, this setting persists between calls to `reset`.
+    pub fn tolerance(&self, tolerance: f32) {
+        let state = self.state.borrow();
+        state.intermediary_state.borrow_mut().tolerance = Some(tolerance);
+    }
+
     // Primitive geometry.
 
-    /// Specify a color with which the background should be cleared.
+    /// Specify a color or gradient with which the background should be cleared.
     pub fn background(&self) -> Background<S> {
         background::new(self)
     }
@@ -370,6 +409,11 @@ where
         self.a(Default::default())
     }
 
+    /// Begin drawing a **Ring**.
+    pub fn ring(&self) -> Drawing<primitive::Ring<S>, S> {
+        self.a(Default::default())
+    }
+
     /// Begin drawing a **Line**.
     pub fn line(&self) -> Drawing<primitive::Line<S>, S> {
         self.a(Default::default())
@@ -395,6 +439,79 @@ where
         self.a(Default::default())
     }
 
+    /// Begin drawing a **Polygon** whose outline is interpolated between the closed outlines `a`
+    /// and `b` by the amount `t`.
+    ///
+    /// If the outlines have a differing number of points, the outline with fewer points is first
+    /// resampled along its perimeter to match the other. See `geom::polygon::morph` for details.
+    pub fn morph(
+        &self,
+        a: &[geom::Point2<S>],
+        b: &[geom::Point2<S>],
+        t: S,
+    ) -> primitive::polygon::DrawingPolygon<S> {
+        let points = geom::polygon::morph(a, b, t);
+        self.polygon().points(points)
+    }
+
+    /// Draw a debug gizmo showing the orientation of the *x*, *y* and *z* axes.
+    ///
+    /// Draws a red line along the positive *x* axis, a green line along the positive *y* axis
+    /// and a blue line along the positive *z* axis, each of the given `length` and starting at the
+    /// origin. This is useful for debugging camera and transform issues within 3D sketches.
+    ///
+    /// Lines are always tessellated within the *xy* plane, so the *z* axis line is produced by
+    /// rotating an *x* axis line a negative quarter turn around the *y* axis, mapping +*x* to +*z*.
+    pub fn axes(&self, length: S) {
+        let origin = geom::pt2(S::zero(), S::zero());
+        let x_end = geom::pt2(length, S::zero());
+        let y_end = geom::pt2(S::zero(), length);
+        let quarter_turn = S::from(-0.25).unwrap();
+        self.line()
+            .points(origin, x_end)
+            .color(color::rgb(1.0, 0.0, 0.0));
+        self.line()
+            .points(origin, y_end)
+            .color(color::rgb(0.0, 1.0, 0.0));
+        self.line()
+            .points(origin, x_end)
+            .y_turns(quarter_turn)
+            .color(color::rgb(0.0, 0.0, 1.0));
+    }
+
+    /// Begin drawing a point cloud where each point is described by its position, size and color.
+    ///
+    /// Each point is drawn as a square of the given size facing the *z* axis, centred on the
+    /// point. This allows for visualising point data at varying scales, whereas points drawn via a
+    /// point list topology are always a single pixel in size. The size is in the same units as the
+    /// positions.
+    ///
+    /// The point cloud is drawn as a single **Mesh** that may be positioned and oriented as a
+    /// whole.
+    pub fn point_cloud<I>(&self, points: I) -> Drawing<primitive::Mesh<S>, S>
+    where
+        I: IntoIterator<Item = (geom::Point3<S>, S, properties::LinSrgba)>,
+    {
+        let mut vertices = vec![];
+        let mut indices = vec![];
+        for (p, size, color) in points {
+            let half = size / (S::one() + S::one());
+            let i = vertices.len();
+            let corners = [(-half, -half), (half, -half), (half, half), (-half, half)];
+            for &(x, y) in corners.iter() {
+                let corner = geom::Point3 {
+                    x: p.x + x,
+                    y: p.y + y,
+                    z: p.z,
+                };
+                vertices.push((corner, color));
+            }
+            indices.push([i, i + 1, i + 2]);
+            indices.push([i, i + 2, i + 3]);
+        }
+        self.mesh().indexed(vertices, indices)
+    }
+
     /// Begin drawing a **Mesh**.
     pub fn mesh(&self) -> Drawing<primitive::mesh::Vertexless, S> {
         self.a(Default::default())
@@ -422,6 +539,50 @@ where
         self.a(text)
     }
 
+    /// Produce the indices of the inner mesh ordered for rendering.
+    ///
+    /// The indices of each primitive are ordered by the primitive's z-order so that primitives
+    /// with a higher z-order are drawn last (on top). Primitives with equal z-orders retain the
+    /// order in which they were drawn. Primitives without a specified z-order have a z-order of
+    /// `0`. If no z-orders were specified, this is the same as the inner mesh indices.
+    ///
+    /// **Note:** If there are any **Drawing**s in progress, these will first be drained and
+    /// completed before the indices are produced.
+    pub fn z_ordered_indices(&self) -> Vec<usize> {
+        self.with_z_ordered_indices(|indices| indices.collect())
+    }
+
+    // Call the given function with an iterator yielding the indices of the inner mesh ordered by
+    // z-order.
+    //
+    // Unlike `z_ordered_indices`, this allows for filling a reusable buffer without an
+    // intermediary allocation of the whole mesh. Only the index ranges of each primitive are
+    // sorted.
+    pub(crate) fn with_z_ordered_indices<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut dyn Iterator<Item = usize>) -> T,
+    {
+        self.finish_remaining_drawings().expect(WOULD_CYCLE);
+        let state = self.state.borrow();
+        let indices = state.mesh.indices();
+        if state.z_orders.is_empty() {
+            return f(&mut indices.iter().cloned());
+        }
+        let mut ranges: Vec<_> = state
+            .ranges
+            .iter()
+            .map(|(n, ranges)| {
+                let z_order = state.z_orders.get(n).cloned().unwrap_or(0);
+                (z_order, ranges.indices.clone())
+            })
+            .collect();
+        ranges.sort_by_key(|&(z_order, ref range)| (z_order, range.start));
+        let mut ordered = ranges
+            .into_iter()
+            .flat_map(|(_, range)| indices[range].iter().cloned());
+        f(&mut ordered)
+    }
+
     /// Produce the transformed mesh vertices for the node at the given index.
     ///
     /// Returns **None** if there is no node for the given index.
@@ -497,11 +658,109 @@ where
         geom::tri::iter_from_vertices(self.vertices())
     }
 
+    /// Record the current contents of the **Draw** so that they may be rendered later.
+    ///
+    /// The transformed vertices, the z-ordered indices and the background are copied into the
+    /// returned **DrawCommands**, which remain unaffected by subsequent calls to `reset`.
+    ///
+    /// **Note:** If there are any **Drawing**s in progress, these will first be drained and
+    /// completed before the commands are recorded.
+    pub fn record(&self) -> DrawCommands<S> {
+        let vertices = self.raw_vertices().collect();
+        let indices = self.z_ordered_indices();
+        let state = self.state.borrow();
+        DrawCommands {
+            vertices,
+            indices,
+            background_color: state.background_color,
+            background_gradient: state.background_gradient,
+        }
+    }
+
     /// Borrow the **Draw**'s inner **Mesh**.
     pub fn inner_mesh(&self) -> Ref<Mesh<S>> {
         Ref::map(self.state.borrow(), |s| &s.mesh)
     }
 
+    /// Export the tessellated geometry as a Wavefront OBJ file at the given path.
+    ///
+    /// The file describes the positions of all vertices, transformed via the inner geometry
+    /// graph, along with a triangular face for each trio of indices. Geometry drawn in 2D is
+    /// exported with a *z* of `0.0`.
+    pub fn export_obj<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let file = std::fs::File::create(path)?;
+        self.write_obj(io::BufWriter::new(file))
+    }
+
+    /// Write the tessellated geometry in the Wavefront OBJ format to the given writer.
+    ///
+    /// See `export_obj` for details.
+    pub fn write_obj<W>(&self, mut w: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(w, "# Exported by nannou")?;
+        for v in self.raw_vertices() {
+            let [x, y, z] = point_to_f64(*v.point());
+            writeln!(w, "v {} {} {}", x, y, z)?;
+        }
+        // OBJ indices begin at `1`.
+        for tri in self.inner_mesh().indices().chunks_exact(3) {
+            writeln!(w, "f {} {} {}", tri[0] + 1, tri[1] + 1, tri[2] + 1)?;
+        }
+        w.flush()
+    }
+
+    /// Export the tessellated geometry as an ASCII PLY file at the given path.
+    ///
+    /// In addition to the positions and triangular faces exported by `export_obj`, the file
+    /// describes the color of each vertex as 8-bit sRGBA.
+    pub fn export_ply<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let file = std::fs::File::create(path)?;
+        self.write_ply(io::BufWriter::new(file))
+    }
+
+    /// Write the tessellated geometry in the ASCII PLY format to the given writer.
+    ///
+    /// See `export_ply` for details.
+    pub fn write_ply<W>(&self, mut w: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let vertices: Vec<_> = self.raw_vertices().collect();
+        let mesh = self.inner_mesh();
+        let faces = mesh.indices().chunks_exact(3);
+        writeln!(w, "ply")?;
+        writeln!(w, "format ascii 1.0")?;
+        writeln!(w, "comment Exported by nannou")?;
+        writeln!(w, "element vertex {}", vertices.len())?;
+        for property in &["x", "y", "z"] {
+            writeln!(w, "property float {}", property)?;
+        }
+        for property in &["red", "green", "blue", "alpha"] {
+            writeln!(w, "property uchar {}", property)?;
+        }
+        writeln!(w, "element face {}", faces.len())?;
+        writeln!(w, "property list uchar uint vertex_indices")?;
+        writeln!(w, "end_header")?;
+        for v in &vertices {
+            let [x, y, z] = point_to_f64(*v.point());
+            let srgba: color::Srgba<u8> = color::Srgba::from_linear(v.color).into_format();
+            let (r, g, b, a) = srgba.into_components();
+            writeln!(w, "{} {} {} {} {} {} {}", x, y, z, r, g, b, a)?;
+        }
+        for tri in faces {
+            writeln!(w, "3 {} {} {}", tri[0], tri[1], tri[2])?;
+        }
+        w.flush()
+    }
+
     // Dimensions methods.
 
     /// The length of the untransformed node at the given index along the axis returned by the
@@ -579,6 +838,28 @@ where
     }
 }
 
+impl<S> DrawCommands<S> {
+    /// The recorded vertices, transformed via the geometry graph of the **Draw**.
+    pub fn vertices(&self) -> &[mesh::Vertex<S>] {
+        &self.vertices
+    }
+
+    /// The recorded indices into `vertices`, ordered for rendering.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The recorded background color, if any.
+    pub fn background_color(&self) -> Option<properties::LinSrgba> {
+        self.background_color
+    }
+
+    /// The recorded background gradient, if any.
+    pub fn background_gradient(&self) -> Option<background::Gradient> {
+        self.background_gradient
+    }
+}
+
 impl<S> Default for IntermediaryState<S> {
     fn default() -> Self {
         let intermediary_mesh = Default::default();
@@ -586,12 +867,14 @@ impl<S> Default for IntermediaryState<S> {
         let path_event_buffer = Default::default();
         let text_buffer = Default::default();
         let glyph_cache = Default::default();
+        let tolerance = None;
         IntermediaryState {
             intermediary_mesh,
             fill_tessellator,
             path_event_buffer,
             text_buffer,
             glyph_cache,
+            tolerance,
         }
     }
 }
@@ -607,9 +890,11 @@ where
         let intermediary_state = RefCell::new(Default::default());
         let mesh = Default::default();
         let ranges = Default::default();
+        let z_orders = Default::default();
         let theme = Default::default();
         let last_node_drawn = Default::default();
         let background_color = Default::default();
+        let background_gradient = Default::default();
         State {
             geom_graph,
             geom_graph_dfs,
@@ -617,9 +902,11 @@ where
             mesh,
             drawing,
             ranges,
+            z_orders,
             theme,
             last_node_drawn,
             background_color,
+            background_gradient,
         }
     }
 }
@@ -861,6 +1148,14 @@ where
     }
 }
 
+// Convert the given mesh point to `f64` coordinates for exporting.
+fn point_to_f64<S: BaseFloat>(p: mesh::vertex::Point<S>) -> [f64; 3] {
+    let x = NumCast::from(p.x).unwrap();
+    let y = NumCast::from(p.y).unwrap();
+    let z = NumCast::from(p.z).unwrap();
+    [x, y, z]
+}
+
 fn point_x<S: Clone>(p: &mesh::vertex::Point<S>) -> S {
     p.x.clone()
 }
@@ -975,6 +1270,7 @@ where
         Primitive::Polygon(prim) => into_drawn(draw, node_index, prim),
         Primitive::Quad(prim) => into_drawn(draw, node_index, prim),
         Primitive::Rect(prim) => into_drawn(draw, node_index, prim),
+        Primitive::Ring(prim) => into_drawn(draw, node_index, prim),
         Primitive::Text(prim) => into_drawn(draw, node_index, prim),
         Primitive::Tri(prim) => into_drawn(draw, node_index, prim),
 
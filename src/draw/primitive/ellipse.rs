@@ -45,12 +45,32 @@ where
     }
 
     /// The number of sides used to draw the ellipse.
+    ///
+    /// If unspecified, the resolution is derived from the tessellation tolerance if one was given,
+    /// otherwise a default resolution of 50 is used.
     pub fn resolution(mut self, resolution: usize) -> Self {
         self.resolution = Some(resolution);
         self
     }
 }
 
+// The number of sides required for the outline of a circle with the given radius to deviate from
+// the true circle by no more than `tolerance`.
+//
+// Returns `None` if the tolerance is not positive.
+pub(crate) fn resolution_from_tolerance(radius: f32, tolerance: f32) -> Option<usize> {
+    const MIN_RESOLUTION: usize = 3;
+    if tolerance <= 0.0 {
+        return None;
+    }
+    if radius <= tolerance {
+        return Some(MIN_RESOLUTION);
+    }
+    let angle = (1.0 - tolerance / radius).acos();
+    let resolution = (std::f32::consts::PI / angle).ceil() as usize;
+    Some(std::cmp::max(resolution, MIN_RESOLUTION))
+}
+
 // Trait implementations.
 
 impl<S> IntoDrawn<S> for Ellipse<S>
@@ -79,11 +99,21 @@ where
         let default_h = || S::from(100.0).unwrap();
         let w = maybe_x.unwrap_or_else(default_w);
         let h = maybe_y.unwrap_or_else(default_h);
-        let resolution = resolution.unwrap_or(DEFAULT_RESOLUTION);
         let rect = geom::Rect::from_wh(Vector2 { x: w, y: h });
-        let ellipse = geom::Ellipse::new(rect, resolution);
-        let points = ellipse.circumference();
-        let polygon = draw.drawing_context(|ctxt| polygon.points(ctxt, points));
+        let polygon = draw.drawing_context(|ctxt| {
+            // If no resolution was specified, derive one from the tessellation tolerance.
+            let resolution = resolution
+                .or_else(|| {
+                    let tolerance = polygon.fill_tolerance(ctxt.tolerance)?;
+                    let radius = w.max(h) / (S::one() + S::one());
+                    let radius: f32 = radius.to_f32().expect("failed to cast radius");
+                    resolution_from_tolerance(radius, tolerance)
+                })
+                .unwrap_or(DEFAULT_RESOLUTION);
+            let ellipse = geom::Ellipse::new(rect, resolution);
+            let points = ellipse.circumference();
+            polygon.points(ctxt, points)
+        });
         polygon.into_drawn_themed(draw, &theme::Primitive::Ellipse)
     }
 }
@@ -174,6 +204,9 @@ where
     }
 
     /// The number of sides used to draw the ellipse.
+    ///
+    /// If unspecified, the resolution is derived from the tessellation tolerance if one was given,
+    /// otherwise a default resolution of 50 is used.
     pub fn resolution(self, resolution: usize) -> Self {
         self.map_ty(|ty| ty.resolution(resolution))
     }
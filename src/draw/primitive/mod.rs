@@ -5,6 +5,7 @@ pub mod path;
 pub mod polygon;
 pub mod quad;
 pub mod rect;
+pub mod ring;
 pub mod text;
 pub mod tri;
 
@@ -17,6 +18,7 @@ pub use self::path::{Path, PathFill, PathInit, PathStroke};
 pub use self::polygon::{Polygon, PolygonInit};
 pub use self::quad::Quad;
 pub use self::rect::Rect;
+pub use self::ring::Ring;
 pub use self::text::Text;
 pub use self::tri::Tri;
 
@@ -39,6 +41,7 @@ pub enum Primitive<S = geom::scalar::Default> {
     Polygon(Polygon<S>),
     Quad(Quad<S>),
     Rect(Rect<S>),
+    Ring(Ring<S>),
     Text(Text<S>),
     Tri(Tri<S>),
 }
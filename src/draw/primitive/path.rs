@@ -149,6 +149,55 @@ impl<S> PathFill<S> {
     }
 }
 
+impl<S> PathFill<S> {
+    /// Submit a sequence of sub-paths to be tessellated, each filled with its own color.
+    ///
+    /// Each sub-path is tessellated separately, so overlapping sub-paths will not affect one
+    /// another's fill.
+    pub(crate) fn colored_subpath_events<I, E>(
+        self,
+        ctxt: DrawingContext<S>,
+        subpaths: I,
+    ) -> Path<S>
+    where
+        S: BaseFloat,
+        I: IntoIterator<Item = (LinSrgba, E)>,
+        E: IntoIterator<Item = PathEvent>,
+    {
+        let DrawingContext {
+            mesh,
+            fill_tessellator,
+            ..
+        } = ctxt;
+        let color = Cell::new(None);
+        let mut builder = mesh.builder();
+        builder.begin_geom();
+        for (subpath_color, events) in subpaths {
+            color.set(Some(subpath_color));
+            let res = self.opts.tessellate(
+                &mut *fill_tessellator,
+                events,
+                &mut SubpathGeometryBuilder(PathGeometryBuilder {
+                    builder: &mut builder,
+                    color: &color,
+                }),
+            );
+            if let Err(err) = res {
+                eprintln!("failed to tessellate sub-path: {:?}", err);
+            }
+        }
+        builder.end_geom();
+        Path::new(
+            self.position,
+            self.orientation,
+            self.color,
+            builder.vertex_data_ranges(),
+            builder.index_range(),
+            builder.min_index(),
+        )
+    }
+}
+
 impl<S> PathStroke<S> {
     /// Short-hand for the `stroke_weight` method.
     pub fn weight(self, weight: f32) -> Self {
@@ -387,15 +436,27 @@ where
     /// Specify that we want to use fill tessellation for the path.
     ///
     /// The returned building context allows for specifying the fill tessellation options.
+    ///
+    /// The fill tolerance is initialised to the default tolerance of the **Draw** if one was
+    /// specified.
     pub fn fill(self) -> DrawingPathFill<'a, S> {
-        self.map_ty(|ty| ty.fill())
+        self.map_ty_with_context(|ty, ctxt| match ctxt.tolerance {
+            Some(tolerance) => ty.fill().tolerance(tolerance),
+            None => ty.fill(),
+        })
     }
 
     /// Specify that we want to use stroke tessellation for the path.
     ///
     /// The returned building context allows for specifying the stroke tessellation options.
+    ///
+    /// The stroke tolerance is initialised to the default tolerance of the **Draw** if one was
+    /// specified.
     pub fn stroke(self) -> DrawingPathStroke<'a, S> {
-        self.map_ty(|ty| ty.stroke())
+        self.map_ty_with_context(|ty, ctxt| match ctxt.tolerance {
+            Some(tolerance) => ty.stroke().tolerance(tolerance),
+            None => ty.stroke(),
+        })
     }
 }
 
@@ -612,6 +673,34 @@ where
     }
 }
 
+// Allows for tessellating multiple sub-paths into the same range of the intermediary mesh by
+// ignoring the beginning and ending of each geometry.
+struct SubpathGeometryBuilder<'a, 'mesh, S>(PathGeometryBuilder<'a, 'mesh, S>);
+
+impl<'a, 'ctxt, S> GeometryBuilder<FillVertex> for SubpathGeometryBuilder<'a, 'ctxt, S>
+where
+    S: BaseFloat,
+{
+    fn begin_geometry(&mut self) {}
+
+    fn end_geometry(&mut self) -> geometry_builder::Count {
+        let vertices = self.0.builder.vertex_data_ranges.points.len() as u32;
+        let indices = self.0.builder.mesh.indices.len() - self.0.builder.index_range.start;
+        let indices = indices as u32;
+        geometry_builder::Count { vertices, indices }
+    }
+
+    fn add_vertex(&mut self, v: FillVertex) -> Result<VertexId, GeometryBuilderError> {
+        GeometryBuilder::<FillVertex>::add_vertex(&mut self.0, v)
+    }
+
+    fn add_triangle(&mut self, a: VertexId, b: VertexId, c: VertexId) {
+        GeometryBuilder::<FillVertex>::add_triangle(&mut self.0, a, b, c);
+    }
+
+    fn abort_geometry(&mut self) {}
+}
+
 impl<S> IntoDrawn<S> for Path<S>
 where
     S: BaseFloat,
@@ -11,7 +11,7 @@ use crate::geom::{self, Point2};
 use crate::math::BaseFloat;
 use lyon::path::iterator::FlattenedIterator;
 use lyon::path::PathEvent;
-use lyon::tessellation::{StrokeOptions, StrokeTessellator};
+use lyon::tessellation::{FillOptions, StrokeOptions, StrokeTessellator};
 use std::ops;
 
 /// A trait implemented for all polygon draw primitives.
@@ -42,6 +42,34 @@ pub trait SetPolygon<S>: Sized {
         *self.polygon_options_mut() = opts;
         self
     }
+
+    /// Maximum allowed distance to the path when building an approximation of curves during
+    /// fill tessellation.
+    ///
+    /// Lower values produce smoother curves at the cost of more triangles. If unspecified, the
+    /// default tolerance of the **Draw** instance is used.
+    fn tolerance(mut self, tolerance: f32) -> Self {
+        self.polygon_options_mut().tolerance = Some(tolerance);
+        self
+    }
+
+    /// Displace each point along the perimeter of the polygon by a pseudo-random offset of up to
+    /// `amount` along each axis prior to tessellation.
+    ///
+    /// This is useful for producing a hand-drawn or sketchy aesthetic. The offsets are determined
+    /// by the given `seed` and the position of each point, so the same seed will always produce
+    /// the same result, across platforms and versions of nannou.
+    fn jitter(mut self, amount: f32, seed: u64) -> Self {
+        self.polygon_options_mut().jitter = Some(Jitter { amount, seed });
+        self
+    }
+}
+
+// Describes the pseudo-random displacement applied to a polygon's perimeter.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Jitter {
+    amount: f32,
+    seed: u64,
 }
 
 /// State related to drawing a **Polygon**.
@@ -59,6 +87,8 @@ pub struct PolygonOptions<S = geom::scalar::Default> {
     stroke_color: Option<LinSrgba>,
     color: Option<LinSrgba>,
     stroke: Option<StrokeOptions>,
+    tolerance: Option<f32>,
+    jitter: Option<Jitter>,
 }
 
 /// A polygon with vertices already submitted.
@@ -96,6 +126,12 @@ impl<S> PolygonInit<S> {
         self.stroke_color(color)
     }
 
+    /// The tolerance that will be used for fill tessellation given the default tolerance of the
+    /// **Draw** instance.
+    pub(crate) fn fill_tolerance(&self, default: Option<f32>) -> Option<f32> {
+        self.opts.tolerance.or(default)
+    }
+
     /// Submit the path events to be tessellated.
     pub(crate) fn events<I>(self, ctxt: DrawingContext<S>, events: I) -> Polygon<S>
     where
@@ -106,16 +142,26 @@ impl<S> PolygonInit<S> {
             mesh,
             fill_tessellator,
             path_event_buffer,
+            tolerance,
             ..
         } = ctxt;
 
         path_event_buffer.clear();
-        path_event_buffer.extend(events);
+        match self.opts.jitter {
+            None => path_event_buffer.extend(events),
+            Some(jitter) => {
+                let events = events.into_iter().map(|e| jitter.event(e));
+                path_event_buffer.extend(events);
+            }
+        }
 
         // Fill tessellation.
         let (fill_vdr, fill_ir, min_index) = if !self.opts.no_fill {
             let mut builder = mesh.builder();
-            let opts = Default::default();
+            let mut opts = FillOptions::default();
+            if let Some(tolerance) = self.fill_tolerance(tolerance) {
+                opts.tolerance = tolerance;
+            }
             let events = path_event_buffer.iter().cloned();
             let res = fill_tessellator.tessellate_path(events, &opts, &mut builder);
             if let Err(err) = res {
@@ -167,18 +213,28 @@ impl<S> PolygonInit<S> {
     }
 
     /// Consumes an iterator of points and converts them to an iterator yielding path events.
+    ///
+    /// The points describe a closed outline which may be concave or self-intersecting. Fewer than
+    /// three points describe no area and produce no geometry.
     pub fn points<I>(self, ctxt: DrawingContext<S>, points: I) -> Polygon<S>
     where
         S: BaseFloat,
         I: IntoIterator,
         I::Item: Into<Point2<S>>,
     {
-        let points = points.into_iter().map(|p| {
-            let p: Point2<f32> = p.into().cast().expect("failed to cast point");
-            p.into()
-        });
+        let points: Vec<lyon::math::Point> = points
+            .into_iter()
+            .map(|p| {
+                let p: Point2<f32> = p.into().cast().expect("failed to cast point");
+                p.into()
+            })
+            .collect();
+        if points.len() < 3 {
+            return self.events(ctxt, std::iter::empty());
+        }
         let close = true;
-        let events = lyon::path::iterator::FromPolyline::new(close, points).path_events();
+        let events =
+            lyon::path::iterator::FromPolyline::new(close, points.into_iter()).path_events();
         self.events(ctxt, events)
     }
 }
@@ -209,6 +265,25 @@ where
     pub fn polygon_options(self, opts: PolygonOptions<S>) -> Self {
         self.map_ty(|ty| ty.polygon_options(opts))
     }
+
+    /// Maximum allowed distance to the path when building an approximation of curves during
+    /// fill tessellation.
+    ///
+    /// Lower values produce smoother curves at the cost of more triangles. If unspecified, the
+    /// default tolerance of the **Draw** instance is used.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_ty(|ty| ty.tolerance(tolerance))
+    }
+
+    /// Displace each point along the perimeter of the polygon by a pseudo-random offset of up to
+    /// `amount` along each axis prior to tessellation.
+    ///
+    /// This is useful for producing a hand-drawn or sketchy aesthetic. The offsets are determined
+    /// by the given `seed` and the position of each point, so the same seed will always produce
+    /// the same result, across platforms and versions of nannou.
+    pub fn jitter(self, amount: f32, seed: u64) -> Self {
+        self.map_ty(|ty| ty.jitter(amount, seed))
+    }
 }
 
 impl<'a, S> DrawingPolygonInit<'a, S>
@@ -233,6 +308,9 @@ where
     }
 
     /// Describe the polygon with a sequence of points.
+    ///
+    /// The outline is closed automatically and triangulated by the fill tessellator, so concave
+    /// and self-intersecting polygons are supported. Fewer than three points produce nothing.
     pub fn points<I>(self, points: I) -> DrawingPolygon<'a, S>
     where
         S: BaseFloat,
@@ -297,6 +375,81 @@ where
     }
 }
 
+impl Jitter {
+    // Displace the given point by an offset determined by the seed and the point's position.
+    //
+    // Using the position ensures that points shared between consecutive events receive the same
+    // displacement. The hash and generator are implemented here rather than relying on `std` or
+    // `rand`, neither of which guarantee stable output across versions.
+    fn point(&self, p: lyon::math::Point) -> lyon::math::Point {
+        if self.amount <= 0.0 {
+            return p;
+        }
+        let hash = [p.x.to_bits(), p.y.to_bits()]
+            .iter()
+            .fold(splitmix64(self.seed), |h, &bits| {
+                splitmix64(h ^ bits as u64)
+            });
+        let mut rng = XorShift64Star(hash | 1);
+        let mut offset = || (rng.next_f32() * 2.0 - 1.0) * self.amount;
+        lyon::math::point(p.x + offset(), p.y + offset())
+    }
+
+    // Displace all points within the given path event.
+    fn event(&self, e: PathEvent) -> PathEvent {
+        use lyon::geom::{CubicBezierSegment, LineSegment, QuadraticBezierSegment};
+        match e {
+            PathEvent::MoveTo(p) => PathEvent::MoveTo(self.point(p)),
+            PathEvent::Line(e) => PathEvent::Line(LineSegment {
+                from: self.point(e.from),
+                to: self.point(e.to),
+            }),
+            PathEvent::Quadratic(e) => PathEvent::Quadratic(QuadraticBezierSegment {
+                from: self.point(e.from),
+                ctrl: self.point(e.ctrl),
+                to: self.point(e.to),
+            }),
+            PathEvent::Cubic(e) => PathEvent::Cubic(CubicBezierSegment {
+                from: self.point(e.from),
+                ctrl1: self.point(e.ctrl1),
+                ctrl2: self.point(e.ctrl2),
+                to: self.point(e.to),
+            }),
+            PathEvent::Close(e) => PathEvent::Close(LineSegment {
+                from: self.point(e.from),
+                to: self.point(e.to),
+            }),
+        }
+    }
+}
+
+// The splitmix64 finalizer, used to hash the jitter seed and point positions.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// A minimal xorshift64* generator. The state must be non-zero.
+struct XorShift64Star(u64);
+
+impl XorShift64Star {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // A value within the range `[0.0, 1.0)` from the upper 24 bits of the next value.
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+}
+
 impl<S> Default for PolygonInit<S> {
     fn default() -> Self {
         let opts = Default::default();
@@ -312,6 +465,8 @@ impl<S> Default for PolygonOptions<S> {
         let color = None;
         let stroke_color = None;
         let stroke = None;
+        let tolerance = None;
+        let jitter = None;
         PolygonOptions {
             position,
             orientation,
@@ -319,6 +474,8 @@ impl<S> Default for PolygonOptions<S> {
             color,
             stroke_color,
             stroke,
+            tolerance,
+            jitter,
         }
     }
 }
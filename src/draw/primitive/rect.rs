@@ -60,12 +60,9 @@ where
             dimensions,
         } = self;
 
-        // If dimensions were specified, scale the points to those dimensions.
-        let (maybe_x, maybe_y, maybe_z) = dimensions.to_scalars(&draw);
-        assert!(
-            maybe_z.is_none(),
-            "z dimension support for rect is unimplemented"
-        );
+        // If dimensions were specified, scale the points to those dimensions. A rect is flat, so
+        // like `Quad` any depth is ignored. Use the position to place the rect along z.
+        let (maybe_x, maybe_y, _maybe_z) = dimensions.to_scalars(&draw);
         let default_w = || S::from(100.0).unwrap();
         let default_h = || S::from(100.0).unwrap();
         let w = maybe_x.unwrap_or_else(default_w);
@@ -0,0 +1,223 @@
+use crate::color::conv::IntoLinSrgba;
+use crate::draw::primitive::ellipse::resolution_from_tolerance;
+use crate::draw::primitive::polygon::{
+    PolygonIndices, PolygonInit, PolygonOptions, PolygonVertices, SetPolygon,
+};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{
+    ColorScalar, Draw, Drawn, IntoDrawn, LinSrgba, SetColor, SetOrientation, SetPosition, SetStroke,
+};
+use crate::draw::{theme, Drawing};
+use crate::geom::{self, Point2, Vector2};
+use crate::math::BaseFloat;
+use lyon::path::iterator::FromPolyline;
+use lyon::tessellation::StrokeOptions;
+
+/// Properties related to drawing a **Ring**, i.e. the filled region between two concentric
+/// circles.
+#[derive(Clone, Debug)]
+pub struct Ring<S = geom::scalar::Default> {
+    inner_radius: Option<S>,
+    outer_radius: Option<S>,
+    resolution: Option<usize>,
+    polygon: PolygonInit<S>,
+}
+
+/// The drawing context for a ring.
+pub type DrawingRing<'a, S = geom::scalar::Default> = Drawing<'a, Ring<S>, S>;
+
+// Ring-specific methods.
+
+impl<S> Ring<S>
+where
+    S: BaseFloat,
+{
+    /// Stroke the inner and outer edges with the given color.
+    pub fn stroke<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.stroke_color(color)
+    }
+
+    /// The radius of the inner edge of the ring.
+    ///
+    /// If the inner radius is greater than or equal to the outer radius, no geometry is produced.
+    pub fn inner_radius(mut self, radius: S) -> Self {
+        self.inner_radius = Some(radius);
+        self
+    }
+
+    /// The radius of the outer edge of the ring.
+    ///
+    /// If the inner radius is greater than or equal to the outer radius, no geometry is produced.
+    pub fn outer_radius(mut self, radius: S) -> Self {
+        self.outer_radius = Some(radius);
+        self
+    }
+
+    /// The number of sides used to draw each edge of the ring.
+    ///
+    /// If unspecified, the resolution is derived from the tessellation tolerance if one was given,
+    /// otherwise a default resolution of 50 is used.
+    pub fn resolution(mut self, resolution: usize) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+}
+
+// Trait implementations.
+
+impl<S> IntoDrawn<S> for Ring<S>
+where
+    S: BaseFloat,
+{
+    type Vertices = PolygonVertices;
+    type Indices = PolygonIndices;
+    fn into_drawn(self, mut draw: Draw<S>) -> Drawn<S, Self::Vertices, Self::Indices> {
+        let Ring {
+            inner_radius,
+            outer_radius,
+            resolution,
+            polygon,
+        } = self;
+
+        // TODO: These should probably be adjustable via Theme.
+        const DEFAULT_RESOLUTION: usize = 50;
+        let outer_radius = outer_radius.unwrap_or_else(|| S::from(50.0).unwrap());
+        let inner_radius = inner_radius.unwrap_or_else(|| S::from(25.0).unwrap());
+        let polygon = draw.drawing_context(|ctxt| {
+            // An inverted or zero-width ring has no area to fill.
+            if inner_radius >= outer_radius {
+                return polygon.events(ctxt, std::iter::empty());
+            }
+
+            // If no resolution was specified, derive one from the tessellation tolerance.
+            let resolution = resolution
+                .or_else(|| {
+                    let tolerance = polygon.fill_tolerance(ctxt.tolerance)?;
+                    let radius: f32 = outer_radius.to_f32().expect("failed to cast radius");
+                    resolution_from_tolerance(radius, tolerance)
+                })
+                .unwrap_or(DEFAULT_RESOLUTION);
+
+            // Describe both edges as closed subpaths. The even-odd fill rule leaves the region
+            // within the inner edge empty.
+            let circle_events = |radius: S| {
+                let side = radius * (S::one() + S::one());
+                let rect = geom::Rect::from_wh(Vector2 { x: side, y: side });
+                let points = geom::Ellipse::new(rect, resolution)
+                    .circumference()
+                    .map(|p| {
+                        let p: Point2<f32> = p.cast().expect("failed to cast point");
+                        p.into()
+                    });
+                let close = true;
+                FromPolyline::new(close, points).path_events()
+            };
+            let events = circle_events(outer_radius).chain(circle_events(inner_radius));
+            polygon.events(ctxt, events)
+        });
+        polygon.into_drawn_themed(draw, &theme::Primitive::Ring)
+    }
+}
+
+impl<S> Default for Ring<S> {
+    fn default() -> Self {
+        let inner_radius = Default::default();
+        let outer_radius = Default::default();
+        let resolution = Default::default();
+        let polygon = Default::default();
+        Ring {
+            inner_radius,
+            outer_radius,
+            resolution,
+            polygon,
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Ring<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.polygon)
+    }
+}
+
+impl<S> SetPosition<S> for Ring<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.polygon)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Ring<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetStroke for Ring<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        SetStroke::stroke_options_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetPolygon<S> for Ring<S> {
+    fn polygon_options_mut(&mut self) -> &mut PolygonOptions<S> {
+        SetPolygon::polygon_options_mut(&mut self.polygon)
+    }
+}
+
+// Primitive conversion.
+
+impl<S> From<Ring<S>> for Primitive<S> {
+    fn from(prim: Ring<S>) -> Self {
+        Primitive::Ring(prim)
+    }
+}
+
+impl<S> Into<Option<Ring<S>>> for Primitive<S> {
+    fn into(self) -> Option<Ring<S>> {
+        match self {
+            Primitive::Ring(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// Drawing methods.
+
+impl<'a, S> DrawingRing<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Stroke the inner and outer edges with the given color.
+    pub fn stroke<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.stroke(color))
+    }
+
+    /// The radius of the inner edge of the ring.
+    ///
+    /// If the inner radius is greater than or equal to the outer radius, no geometry is produced.
+    pub fn inner_radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.inner_radius(radius))
+    }
+
+    /// The radius of the outer edge of the ring.
+    ///
+    /// If the inner radius is greater than or equal to the outer radius, no geometry is produced.
+    pub fn outer_radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.outer_radius(radius))
+    }
+
+    /// The number of sides used to draw each edge of the ring.
+    ///
+    /// If unspecified, the resolution is derived from the tessellation tolerance if one was given,
+    /// otherwise a default resolution of 50 is used.
+    pub fn resolution(self, resolution: usize) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::color::conv::IntoLinSrgba;
 use crate::draw::drawing::DrawingContext;
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{self, dimension, orientation, position};
@@ -22,6 +23,8 @@ pub struct Text<S = geom::scalar::Default> {
 #[derive(Clone, Debug, Default)]
 pub struct Style {
     pub color: Option<LinSrgba>,
+    /// Colors assigned to each glyph in order. If empty, all glyphs use `color`.
+    pub glyph_colors: Vec<LinSrgba>,
     pub layout: text::layout::Builder,
 }
 
@@ -148,6 +151,18 @@ impl<S> Text<S> {
         self.style = style;
         self
     }
+
+    /// Specify a color for each glyph in order.
+    ///
+    /// If there are fewer colors than glyphs, the last color is used for all remaining glyphs.
+    pub fn glyph_colors<I>(mut self, colors: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoLinSrgba<ColorScalar>,
+    {
+        self.style.glyph_colors = colors.into_iter().map(|c| c.into_lin_srgba()).collect();
+        self
+    }
 }
 
 impl<'a, S> DrawingText<'a, S>
@@ -235,6 +250,17 @@ where
     pub fn layout(self, layout: &Layout) -> Self {
         self.map_ty(|ty| ty.layout(layout))
     }
+
+    /// Specify a color for each glyph in order.
+    ///
+    /// If there are fewer colors than glyphs, the last color is used for all remaining glyphs.
+    pub fn glyph_colors<I>(self, colors: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.glyph_colors(colors))
+    }
 }
 
 impl<S> IntoDrawn<S> for Text<S>
@@ -249,7 +275,11 @@ where
             style,
             text,
         } = self;
-        let Style { color, layout } = style;
+        let Style {
+            color,
+            glyph_colors,
+            layout,
+        } = style;
         let layout = layout.build();
         let (maybe_x, maybe_y, maybe_z) = spatial.dimensions.to_scalars(&draw);
         assert!(
@@ -271,6 +301,7 @@ where
                 path_event_buffer,
                 text_buffer,
                 glyph_cache,
+                tolerance,
             } = ctxt;
             let text_str = &text_buffer[text.clone()];
             let text = text::text(text_str).layout(&layout).build(rect);
@@ -292,8 +323,19 @@ where
                 path_event_buffer,
                 glyph_cache,
                 text_buffer: &mut empty_text,
+                tolerance,
+            };
+            let last_color = glyph_colors.last().cloned();
+            let mut path = match last_color {
+                None => path.fill().color(color).events(ctxt, text.path_events()),
+                Some(last_color) => {
+                    let colors = glyph_colors
+                        .into_iter()
+                        .chain(std::iter::repeat(last_color));
+                    let subpaths = colors.zip(text.glyph_path_events());
+                    path.fill().colored_subpath_events(ctxt, subpaths)
+                }
             };
-            let mut path = path.fill().color(color).events(ctxt, text.path_events());
             *SetPosition::properties(&mut path) = spatial.position;
             *SetOrientation::properties(&mut path) = spatial.orientation;
             path
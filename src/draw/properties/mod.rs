@@ -167,6 +167,7 @@ where
             ref mut path_event_buffer,
             ref mut text_buffer,
             ref mut glyph_cache,
+            tolerance,
         } = *intermediary_state;
         f(DrawingContext {
             mesh: intermediary_mesh,
@@ -174,6 +175,7 @@ where
             path_event_buffer: path_event_buffer,
             text_buffer: text_buffer,
             glyph_cache: glyph_cache,
+            tolerance: tolerance,
         })
     }
 }
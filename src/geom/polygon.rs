@@ -1,5 +1,6 @@
 use crate::geom::tri::{self, Tri};
-use crate::geom::{Cuboid, Rect, Vertex, Vertex2d, Vertex3d};
+use crate::geom::{pt2, Cuboid, Point2, Rect, Vertex, Vertex2d, Vertex3d};
+use crate::math::BaseFloat;
 
 /// A simple type wrapper around a list of points that describe a polygon.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -108,6 +109,82 @@ pub fn triangle_indices(n_points: usize) -> TriangleIndices {
     TriangleIndices { index, n_points }
 }
 
+/// Linearly interpolate between the closed outlines `a` and `b` by the amount `t`.
+///
+/// Points are paired by index, so `t = 0.0` produces `a` and `t = 1.0` produces `b`. If the
+/// outlines have a differing number of points, the outline with fewer points is first resampled
+/// at evenly spaced intervals along its perimeter to match the other.
+///
+/// Returns an empty `Vec` if either outline is empty.
+pub fn morph<S>(a: &[Point2<S>], b: &[Point2<S>], t: S) -> Vec<Point2<S>>
+where
+    S: BaseFloat,
+{
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let (a, b) = match a.len().cmp(&b.len()) {
+        std::cmp::Ordering::Less => (resample(a, b.len()), b.to_vec()),
+        std::cmp::Ordering::Greater => (a.to_vec(), resample(b, a.len())),
+        std::cmp::Ordering::Equal => (a.to_vec(), b.to_vec()),
+    };
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| pt2(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t))
+        .collect()
+}
+
+/// Resample the closed outline described by `points` to `n` points spaced evenly along its
+/// perimeter, beginning at the first point.
+///
+/// Returns an empty `Vec` if `points` is empty.
+pub fn resample<S>(points: &[Point2<S>], n: usize) -> Vec<Point2<S>>
+where
+    S: BaseFloat,
+{
+    let first = match points.first() {
+        None => return vec![],
+        Some(&p) => p,
+    };
+
+    // Each edge of the closed outline, including the edge from the last point to the first.
+    let next = points.iter().skip(1).chain(Some(&first));
+    let edges: Vec<_> = points
+        .iter()
+        .zip(next)
+        .map(|(&start, &end)| {
+            let len = (end.x - start.x).hypot(end.y - start.y);
+            (start, end, len)
+        })
+        .collect();
+    let perimeter = edges.iter().fold(S::zero(), |acc, &(_, _, len)| acc + len);
+    if perimeter <= S::zero() {
+        return vec![first; n];
+    }
+
+    let n_s = S::from(n).expect("failed to cast point count");
+    let mut resampled = Vec::with_capacity(n);
+    let mut edge_index = 0;
+    let mut edge_start_dist = S::zero();
+    for i in 0..n {
+        let dist = perimeter * S::from(i).expect("failed to cast point index") / n_s;
+        while edge_index < edges.len() - 1 && edge_start_dist + edges[edge_index].2 < dist {
+            edge_start_dist = edge_start_dist + edges[edge_index].2;
+            edge_index += 1;
+        }
+        let (start, end, len) = edges[edge_index];
+        let t = match len > S::zero() {
+            true => (dist - edge_start_dist) / len,
+            false => S::zero(),
+        };
+        resampled.push(pt2(
+            start.x + (end.x - start.x) * t,
+            start.y + (end.y - start.y) * t,
+        ));
+    }
+    resampled
+}
+
 /// Returns `Some` with the touched triangle if the given `Point` is over the polygon described by
 /// the given series of points.
 ///
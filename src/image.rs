@@ -1,5 +1,149 @@
-//! Items related to working with images. Currently, this module simply re-exports the renown
+//! Items related to working with images. This module re-exports the renown
 //! [image crate](https://docs.rs/image) which supports reading and writing PNG, JPEG, GIF, WEBP,
-//! BMP and more.
+//! BMP and more, along with some helpers for recording captured frames.
 
 pub use image::*;
+
+use std::fmt;
+use std::io::Write;
+
+/// Accumulates captured frames and encodes them as an animated GIF.
+///
+/// Frames are typically produced by reading back a captured texture, e.g. via
+/// `wgpu::TextureSnapshot::read_threaded`. All frames must have the same dimensions. A palette is
+/// generated for each frame as it is encoded during `finish`.
+///
+/// As every frame is kept in memory until `finish` is called, the number of frames and the size
+/// of each frame are limited in order to avoid accidentally producing huge files.
+#[derive(Clone, Debug)]
+pub struct GifRecorder {
+    frames: Vec<RgbaImage>,
+    delay_ms: u32,
+    max_frames: usize,
+    max_side: u32,
+}
+
+/// Errors that might occur while recording or encoding a GIF.
+#[derive(Debug)]
+pub enum GifRecorderError {
+    /// The recorder already holds the maximum number of frames.
+    TooManyFrames { max: usize },
+    /// The width or height of the frame exceeds the maximum side length.
+    FrameTooLarge { size: [u32; 2], max_side: u32 },
+    /// The frame's dimensions differ from those of the first recorded frame.
+    FrameSizeMismatch {
+        expected: [u32; 2],
+        actual: [u32; 2],
+    },
+    /// An error occurred while encoding the GIF.
+    Encode(ImageError),
+}
+
+impl GifRecorder {
+    /// The maximum number of frames that may be recorded by default.
+    pub const DEFAULT_MAX_FRAMES: usize = 1_000;
+    /// The maximum width or height of a recorded frame by default.
+    pub const DEFAULT_MAX_SIDE: u32 = 1_024;
+
+    /// Begin recording a GIF where each frame is displayed for `delay_ms` milliseconds.
+    pub fn new(delay_ms: u32) -> Self {
+        GifRecorder {
+            frames: vec![],
+            delay_ms,
+            max_frames: Self::DEFAULT_MAX_FRAMES,
+            max_side: Self::DEFAULT_MAX_SIDE,
+        }
+    }
+
+    /// Specify the maximum number of frames that may be recorded.
+    pub fn max_frames(mut self, max_frames: usize) -> Self {
+        self.max_frames = max_frames;
+        self
+    }
+
+    /// Specify the maximum width or height of a recorded frame.
+    pub fn max_side(mut self, max_side: u32) -> Self {
+        self.max_side = max_side;
+        self
+    }
+
+    /// The number of frames recorded so far.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether or not no frames have been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Add a frame to the end of the recording.
+    ///
+    /// Returns an error if the recording is full, if the frame exceeds the maximum side length or
+    /// if its dimensions differ from those of the first frame.
+    pub fn add_frame(&mut self, frame: RgbaImage) -> Result<(), GifRecorderError> {
+        if self.frames.len() >= self.max_frames {
+            let max = self.max_frames;
+            return Err(GifRecorderError::TooManyFrames { max });
+        }
+        let (w, h) = frame.dimensions();
+        let size = [w, h];
+        if w > self.max_side || h > self.max_side {
+            let max_side = self.max_side;
+            return Err(GifRecorderError::FrameTooLarge { size, max_side });
+        }
+        if let Some(first) = self.frames.first() {
+            let (fw, fh) = first.dimensions();
+            let expected = [fw, fh];
+            if expected != size {
+                let actual = size;
+                return Err(GifRecorderError::FrameSizeMismatch { expected, actual });
+            }
+        }
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    /// Quantize and encode all recorded frames as an animated GIF to the given writer.
+    pub fn finish<W>(self, w: W) -> Result<(), GifRecorderError>
+    where
+        W: Write,
+    {
+        let mut encoder = gif::GifEncoder::new(w);
+        let delay = Delay::from_numer_denom_ms(self.delay_ms, 1);
+        for buffer in self.frames {
+            let frame = Frame::from_parts(buffer, 0, 0, delay);
+            encoder.encode_frame(frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<ImageError> for GifRecorderError {
+    fn from(err: ImageError) -> Self {
+        GifRecorderError::Encode(err)
+    }
+}
+
+impl std::error::Error for GifRecorderError {}
+
+impl fmt::Display for GifRecorderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GifRecorderError::TooManyFrames { max } => {
+                write!(f, "the GIF recording is limited to {} frames", max)
+            }
+            GifRecorderError::FrameTooLarge { size, max_side } => write!(
+                f,
+                "frame of size {:?} exceeds the maximum side length of {}",
+                size, max_side,
+            ),
+            GifRecorderError::FrameSizeMismatch { expected, actual } => write!(
+                f,
+                "expected a frame of size {:?} but the frame was {:?}",
+                expected, actual,
+            ),
+            GifRecorderError::Encode(ref err) => write!(f, "failed to encode GIF: {}", err),
+        }
+    }
+}
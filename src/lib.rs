@@ -29,6 +29,7 @@ pub use crate::app::{App, LoopMode};
 pub use crate::draw::Draw;
 
 pub mod app;
+pub mod camera;
 pub mod color;
 pub mod draw;
 pub mod ease;
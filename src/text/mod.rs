@@ -420,41 +420,17 @@ impl<'a> Text<'a> {
 
     /// Produce an iterator yielding the path events for every glyph in every line.
     pub fn path_events<'b>(&'b self) -> impl 'b + Iterator<Item = lyon::path::PathEvent> {
-        use lyon::geom::{CubicBezierSegment, LineSegment, QuadraticBezierSegment};
-        use lyon::path::PathEvent;
-
-        // Translate the given lyon point by the given vector.
-        fn trans_lyon_point(p: &lyon::math::Point, v: geom::Vector2) -> lyon::math::Point {
-            lyon::math::point(p.x + v.x, p.y + v.y)
-        }
-
-        // Translate the given path event in 2D space.
-        fn trans_path_event(e: &PathEvent, v: geom::Vector2) -> PathEvent {
-            match *e {
-                PathEvent::MoveTo(ref p) => PathEvent::MoveTo(trans_lyon_point(p, v)),
-                PathEvent::Line(ref e) => PathEvent::Line(LineSegment {
-                    from: trans_lyon_point(&e.from, v),
-                    to: trans_lyon_point(&e.to, v),
-                }),
-                PathEvent::Quadratic(ref e) => PathEvent::Quadratic(QuadraticBezierSegment {
-                    from: trans_lyon_point(&e.from, v),
-                    ctrl: trans_lyon_point(&e.ctrl, v),
-                    to: trans_lyon_point(&e.to, v),
-                }),
-                PathEvent::Cubic(ref e) => PathEvent::Cubic(CubicBezierSegment {
-                    from: trans_lyon_point(&e.from, v),
-                    ctrl1: trans_lyon_point(&e.ctrl1, v),
-                    ctrl2: trans_lyon_point(&e.ctrl2, v),
-                    to: trans_lyon_point(&e.to, v),
-                }),
-                PathEvent::Close(ref e) => PathEvent::Close(LineSegment {
-                    from: trans_lyon_point(&e.from, v),
-                    to: trans_lyon_point(&e.to, v),
-                }),
-            }
-        }
+        self.glyph_path_events().flat_map(std::convert::identity)
+    }
 
-        self.glyphs().flat_map(|(g, r)| {
+    /// Produce an iterator yielding the path events for each glyph in every line.
+    ///
+    /// This is the same as `path_events`, but yields a separate iterator of events for each glyph,
+    /// allowing each glyph to be treated individually, e.g. for per-glyph coloring.
+    pub fn glyph_path_events<'b>(
+        &'b self,
+    ) -> impl 'b + Iterator<Item = impl 'b + Iterator<Item = lyon::path::PathEvent>> {
+        self.glyphs().map(|(g, r)| {
             glyph::path_events(g)
                 .into_iter()
                 .flat_map(|es| es)
@@ -658,3 +634,36 @@ pub fn pt_to_scale(font_size_in_points: FontSize) -> Scale {
 pub fn text(s: &str) -> Builder {
     Builder::from(s)
 }
+
+// Translate the given lyon point by the given vector.
+fn trans_lyon_point(p: &lyon::math::Point, v: geom::Vector2) -> lyon::math::Point {
+    lyon::math::point(p.x + v.x, p.y + v.y)
+}
+
+// Translate the given path event in 2D space.
+fn trans_path_event(e: &lyon::path::PathEvent, v: geom::Vector2) -> lyon::path::PathEvent {
+    use lyon::geom::{CubicBezierSegment, LineSegment, QuadraticBezierSegment};
+    use lyon::path::PathEvent;
+    match *e {
+        PathEvent::MoveTo(ref p) => PathEvent::MoveTo(trans_lyon_point(p, v)),
+        PathEvent::Line(ref e) => PathEvent::Line(LineSegment {
+            from: trans_lyon_point(&e.from, v),
+            to: trans_lyon_point(&e.to, v),
+        }),
+        PathEvent::Quadratic(ref e) => PathEvent::Quadratic(QuadraticBezierSegment {
+            from: trans_lyon_point(&e.from, v),
+            ctrl: trans_lyon_point(&e.ctrl, v),
+            to: trans_lyon_point(&e.to, v),
+        }),
+        PathEvent::Cubic(ref e) => PathEvent::Cubic(CubicBezierSegment {
+            from: trans_lyon_point(&e.from, v),
+            ctrl1: trans_lyon_point(&e.ctrl1, v),
+            ctrl2: trans_lyon_point(&e.ctrl2, v),
+            to: trans_lyon_point(&e.to, v),
+        }),
+        PathEvent::Close(ref e) => PathEvent::Close(LineSegment {
+            from: trans_lyon_point(&e.from, v),
+            to: trans_lyon_point(&e.to, v),
+        }),
+    }
+}
@@ -131,6 +131,28 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Specify a binding for each of the given resources at once.
+    ///
+    /// This is equivalent to calling `binding` for each resource in order, and is useful for
+    /// bind groups that are described by data, e.g. generated bindings. Storage textures are
+    /// bound via `wgpu::BindingResource::TextureView`.
+    pub fn entries(mut self, resources: &[wgpu::BindingResource<'a>]) -> Self {
+        for resource in resources {
+            let resource = match *resource {
+                wgpu::BindingResource::Buffer { buffer, ref range } => {
+                    let range = range.clone();
+                    wgpu::BindingResource::Buffer { buffer, range }
+                }
+                wgpu::BindingResource::Sampler(sampler) => wgpu::BindingResource::Sampler(sampler),
+                wgpu::BindingResource::TextureView(view) => {
+                    wgpu::BindingResource::TextureView(view)
+                }
+            };
+            self = self.binding(resource);
+        }
+        self
+    }
+
     /// Specify a slice of a buffer to be bound.
     ///
     /// The given `range` represents the start and end point of the buffer to be bound in bytes.
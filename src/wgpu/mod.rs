@@ -7,6 +7,11 @@
 //! This module re-exports the entire `wgpu` crate along with all of its documentation while also
 //! adding some additional items that makes `wgpu` easier to use alongside nannou.
 //!
+//! The per-texel math of the texture passes is also available on the CPU via
+//! `ColorConversion::convert`, `ToneMapOperator::map`, `TextureFxaaReshaper::luma`,
+//! `CompositeMode::composite` and `Colormap::map`. Each mirrors the corresponding fragment shader,
+//! which is useful for producing the same results without a GPU, e.g. for a legend or a fallback.
+//!
 //! Useful links:
 //!
 //! - An awesome [guide for wgpu-rs](https://sotrh.github.io/learn-wgpu/#what-is-wgpu). Highly
@@ -39,17 +44,27 @@ pub use self::render_pass::{
 pub use self::render_pipeline_builder::{RenderPipelineBuilder, VertexDescriptor};
 pub use self::sampler_builder::SamplerBuilder;
 pub use self::texture::capturer::{
-    Capturer as TextureCapturer, Rgba8AsyncMapping, Snapshot as TextureSnapshot,
+    unpremultiply_alpha, Capturer as TextureCapturer, Rgba8AsyncMapping,
+    Snapshot as TextureSnapshot,
 };
+pub use self::texture::colormap_reshaper::{Colormap, ColormapReshaper as TextureColormapReshaper};
+pub use self::texture::compositor::{CompositeMode, Compositor as TextureCompositor};
+pub use self::texture::fxaa_reshaper::FxaaReshaper as TextureFxaaReshaper;
 pub use self::texture::image::{
     format_from_image_color_type as texture_format_from_image_color_type, BufferImage,
     ImageAsyncMapping,
 };
-pub use self::texture::reshaper::Reshaper as TextureReshaper;
+pub use self::texture::reshaper::{
+    Builder as TextureReshaperBuilder, ColorConversion, Eye, Reshaper as TextureReshaper,
+    SrgbConversion,
+};
+pub use self::texture::tone_map_reshaper::{
+    ToneMapOperator, ToneMapReshaper as TextureToneMapReshaper,
+};
 pub use self::texture::{
-    descriptor_eq as texture_descriptor_eq, extent_3d_eq,
+    descriptor_eq as texture_descriptor_eq, extent_3d_eq, format_is_srgb as texture_format_is_srgb,
     format_size_bytes as texture_format_size_bytes, BufferBytes, Builder as TextureBuilder,
-    Texture,
+    Error as TextureError, Texture,
 };
 #[doc(inline)]
 pub use wgpu::{
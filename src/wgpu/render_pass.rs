@@ -22,6 +22,8 @@ impl<'a> ColorAttachmentDescriptorBuilder<'a> {
     pub const DEFAULT_LOAD_OP: wgpu::LoadOp = wgpu::LoadOp::Clear;
     pub const DEFAULT_STORE_OP: wgpu::StoreOp = wgpu::StoreOp::Store;
     pub const DEFAULT_CLEAR_COLOR: wgpu::Color = wgpu::Color::TRANSPARENT;
+    /// The load operation used by `dont_care` when the previous contents are irrelevant.
+    pub const DONT_CARE_LOAD_OP: wgpu::LoadOp = wgpu::LoadOp::Clear;
 
     /// Begin building a new render pass color attachment descriptor.
     fn new(attachment: &'a wgpu::TextureView) -> Self {
@@ -48,6 +50,22 @@ impl<'a> ColorAttachmentDescriptorBuilder<'a> {
         self
     }
 
+    /// Indicate that the previous contents of the attachment are irrelevant, e.g. because every
+    /// pixel is about to be overwritten.
+    ///
+    /// This selects the most efficient load operation available for undefined previous contents,
+    /// avoiding a costly restore of the attachment from memory on tiled GPUs. The load operations
+    /// exposed by wgpu are portable across backends and do not include a true "don't care"
+    /// operation, so this currently maps to `DONT_CARE_LOAD_OP`, clearing the attachment to the
+    /// current clear color. Clearing is typically free on tiled GPUs as no memory is read.
+    ///
+    /// Code should not rely on the resulting contents of pixels that are not overwritten, as the
+    /// chosen operation may change should a more efficient option become available.
+    pub fn dont_care(mut self) -> Self {
+        self.descriptor.load_op = Self::DONT_CARE_LOAD_OP;
+        self
+    }
+
     /// The end-of-pass store operation for this color attachment.
     pub fn store_op(mut self, store_op: wgpu::StoreOp) -> Self {
         self.descriptor.store_op = store_op;
@@ -145,6 +163,27 @@ impl<'a> Builder<'a> {
         Self::default()
     }
 
+    /// Begin building a render pass that clears the given color attachment to `color` while
+    /// preserving the existing contents of the given depth stencil attachment.
+    ///
+    /// This is a common pattern in multi-pass rendering where depth is produced by an earlier
+    /// prepass. Both the depth and stencil contents are loaded rather than cleared.
+    pub fn clear_color_keep_depth(
+        color_attachment: &'a wgpu::TextureView,
+        color: wgpu::Color,
+        depth_stencil_attachment: &'a wgpu::TextureView,
+    ) -> Self {
+        Self::new()
+            .color_attachment(color_attachment, |builder| {
+                builder.load_op(wgpu::LoadOp::Clear).clear_color(color)
+            })
+            .depth_stencil_attachment(depth_stencil_attachment, |builder| {
+                builder
+                    .depth_load_op(wgpu::LoadOp::Load)
+                    .stencil_load_op(wgpu::LoadOp::Load)
+            })
+    }
+
     /// Add a single color attachment descriptor to the render pass descriptor.
     ///
     /// Call this multiple times in succession to add multiple color attachments.
@@ -282,6 +282,16 @@ impl<'a> RenderPipelineBuilder<'a> {
         self
     }
 
+    /// Specify the color states for each of the output attachments.
+    ///
+    /// This is useful for pipelines that output to multiple render targets. Each color state may
+    /// specify its own format and blend state. When specified, this takes precedence over the
+    /// single color state produced by the `color_state` family of methods.
+    pub fn color_states(mut self, states: &'a [wgpu::ColorStateDescriptor]) -> Self {
+        self.color_states = states;
+        self
+    }
+
     // Depth / Stencil state
 
     pub fn depth_stencil_state(mut self, state: wgpu::DepthStencilStateDescriptor) -> Self {
@@ -390,6 +400,13 @@ impl<'a> RenderPipelineBuilder<'a> {
         self
     }
 
+    /// The mask of samples that may be written, where each bit enables the sample of the same
+    /// index.
+    pub fn sample_mask(mut self, sample_mask: u32) -> Self {
+        self.sample_mask = sample_mask;
+        self
+    }
+
     // Finalising methods.
 
     /// Build the render pipeline layout, its descriptor and ultimately the pipeline itself with
@@ -479,11 +496,11 @@ fn build(
             &single_color_state[..]
         }
         (true, false) => color_states,
-        (false, true) => panic!("specified color states but no fragment shader"),
-        (false, false) => match color_state.is_some() {
+        (false, true) => match color_state.is_some() {
             true => panic!("specified color state fields but no fragment shader"),
             false => &[],
         },
+        (false, false) => panic!("specified color states but no fragment shader"),
     };
 
     let vertex_buffers: Vec<_> = vertex_buffers
@@ -1,3 +1,4 @@
+use crate::color;
 use crate::wgpu;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex};
@@ -17,6 +18,7 @@ pub struct Capturer {
     converter_data_pair: Mutex<Option<ConverterDataPair>>,
     thread_pool: Arc<Mutex<Option<Arc<ThreadPool>>>>,
     num_threads: Option<usize>,
+    unpremultiply_alpha: bool,
 }
 
 /// A snapshot captured by a **Capturer**.
@@ -27,6 +29,7 @@ pub struct Snapshot {
     buffer: wgpu::BufferImage,
     thread_pool: Arc<Mutex<Option<Arc<ThreadPool>>>>,
     num_threads: Option<usize>,
+    unpremultiply_alpha: bool,
 }
 
 /// A wrapper around a slice of bytes representing a non-linear sRGBA image.
@@ -45,9 +48,10 @@ struct ConverterDataPair {
 }
 
 /// An alias for the image buffer that can be read from a captured **Snapshot**.
-pub struct Rgba8AsyncMappedImageBuffer<'a>(
-    image::ImageBuffer<image::Rgba<u8>, Rgba8AsyncMapping<'a>>,
-);
+pub struct Rgba8AsyncMappedImageBuffer<'a> {
+    buffer: image::ImageBuffer<image::Rgba<u8>, Rgba8AsyncMapping<'a>>,
+    unpremultiply_alpha: bool,
+}
 
 impl Capturer {
     /// The format to which textures will be converted before being mapped back to the CPU.
@@ -74,9 +78,30 @@ impl Capturer {
             converter_data_pair: Default::default(),
             thread_pool: Default::default(),
             num_threads: Some(num_threads),
+            unpremultiply_alpha: false,
         }
     }
 
+    /// Specify whether or not owned images produced from snapshots should have their color
+    /// channels divided by alpha, producing straight (unassociated) alpha.
+    ///
+    /// Textures rendered by the draw renderer hold premultiplied alpha: color is blended by the
+    /// source alpha while alpha is blended by `One`, so geometry drawn over a transparent
+    /// background stores `(c * a, a)`. Image formats like PNG expect straight alpha, so writing
+    /// such a capture directly makes soft edges appear dark when the image is later composited.
+    /// Enabling this recovers the straight color of each pixel so that captures composite
+    /// cleanly in other tools.
+    ///
+    /// Conversion is applied on the CPU when the mapped image is converted to an owned image, i.e.
+    /// via `Rgba8AsyncMappedImageBuffer::to_owned` or `Snapshot::read_threaded`. The mapped memory
+    /// itself is left untouched. See the `unpremultiply_alpha` function.
+    ///
+    /// By default, this is `false`.
+    pub fn unpremultiply_alpha(mut self, unpremultiply: bool) -> Self {
+        self.unpremultiply_alpha = unpremultiply;
+        self
+    }
+
     /// Capture the given texture at the state of the given command encoder.
     pub fn capture(
         &self,
@@ -122,6 +147,7 @@ impl Capturer {
             buffer: buffer_image,
             thread_pool: self.thread_pool.clone(),
             num_threads: self.num_threads,
+            unpremultiply_alpha: self.unpremultiply_alpha,
         }
     }
 
@@ -158,13 +184,16 @@ impl Snapshot {
         F: 'static + FnOnce(Result<Rgba8AsyncMappedImageBuffer, ()>),
     {
         let [width, height] = self.buffer.size();
+        let unpremultiply_alpha = self.unpremultiply_alpha;
         self.buffer.read(move |result| {
             let result = result.map(move |mapping| {
                 let mapping = Rgba8AsyncMapping { mapping };
-                Rgba8AsyncMappedImageBuffer(
-                    image::ImageBuffer::from_raw(width, height, mapping)
-                        .expect("image buffer dimensions did not match mapping"),
-                )
+                let buffer = image::ImageBuffer::from_raw(width, height, mapping)
+                    .expect("image buffer dimensions did not match mapping");
+                Rgba8AsyncMappedImageBuffer {
+                    buffer,
+                    unpremultiply_alpha,
+                }
             });
             callback(result);
         })
@@ -205,11 +234,18 @@ impl Snapshot {
 
 impl<'a> Rgba8AsyncMappedImageBuffer<'a> {
     /// Convert the mapped image buffer to an owned buffer.
+    ///
+    /// If the **Capturer** was configured to unpremultiply alpha, the color channels of the owned
+    /// image will be divided by alpha.
     pub fn to_owned(&self) -> image::ImageBuffer<image::Rgba<u8>, Vec<u8>> {
         let vec = self.as_flat_samples().as_slice().to_vec();
         let (width, height) = self.dimensions();
-        image::ImageBuffer::from_raw(width, height, vec)
-            .expect("image buffer dimensions do not match vec len")
+        let mut image = image::ImageBuffer::from_raw(width, height, vec)
+            .expect("image buffer dimensions do not match vec len");
+        if self.unpremultiply_alpha {
+            unpremultiply_alpha(&mut image);
+        }
+        image
     }
 }
 
@@ -229,7 +265,7 @@ impl<'a> Deref for Rgba8AsyncMapping<'a> {
 impl<'a> Deref for Rgba8AsyncMappedImageBuffer<'a> {
     type Target = image::ImageBuffer<image::Rgba<u8>, Rgba8AsyncMapping<'a>>;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.buffer
     }
 }
 
@@ -239,6 +275,30 @@ impl<'a> AsRef<[u8]> for Rgba8AsyncMapping<'a> {
     }
 }
 
+/// Divide the color channels of each pixel in the given premultiplied non-linear sRGBA image by
+/// its alpha, producing straight alpha.
+///
+/// The division is performed in linear space, as blending occurs before the color is encoded as
+/// sRGB. Fully transparent and fully opaque pixels are left unchanged.
+pub fn unpremultiply_alpha(image: &mut image::ImageBuffer<image::Rgba<u8>, Vec<u8>>) {
+    for pixel in image.pixels_mut() {
+        let a = pixel[3];
+        if a == 0 || a == 255 {
+            continue;
+        }
+        let a = a as f32 / 255.0;
+        let unorm8 = |c: u8| c as f32 / 255.0;
+        let lin = color::Srgb::new(unorm8(pixel[0]), unorm8(pixel[1]), unorm8(pixel[2]));
+        let lin = lin.into_linear();
+        let straight = |c: f32| (c / a).min(1.0);
+        let lin = color::LinSrgb::new(straight(lin.red), straight(lin.green), straight(lin.blue));
+        let srgb: color::Srgb<u8> = color::Srgb::<f32>::from_linear(lin).into_format();
+        pixel[0] = srgb.red;
+        pixel[1] = srgb.green;
+        pixel[2] = srgb.blue;
+    }
+}
+
 // Create the format converter and the target texture.
 fn create_converter_data_pair(
     device: &wgpu::Device,
@@ -0,0 +1,230 @@
+use super::quad::FullscreenQuad;
+use crate::wgpu;
+
+/// Writes a single-channel scalar field texture to a color texture by mapping the red channel
+/// through a colormap.
+///
+/// This is useful for visualising data such as heat-maps, where the source texture (e.g.
+/// `R32Float`) holds a scalar per texel. Values are clamped to the range `0.0..=1.0` before being
+/// mapped, so the data should be normalised ahead of time.
+///
+/// The `src_texture` must have the `TextureUsage::SAMPLED` enabled and must not be multisampled.
+/// Multisampled textures should first be resolved.
+///
+/// The `dst_texture` must have the `TextureUsage::OUTPUT_ATTACHMENT` enabled.
+#[derive(Debug)]
+pub struct ColormapReshaper {
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    quad: FullscreenQuad,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    colormap: Colormap,
+}
+
+/// A colormap used to map scalar values to colors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Colormap {
+    /// Matplotlib's perceptually uniform default colormap, from dark purple to yellow.
+    Viridis,
+    /// Matplotlib's perceptually uniform colormap from black through purple to pale yellow.
+    Magma,
+    /// The classic rainbow colormap from dark blue through cyan and yellow to dark red.
+    Jet,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Uniforms {
+    colormap: u32,
+}
+
+// Evenly spaced stops sampled from matplotlib's viridis colormap.
+const VIRIDIS: [[f32; 3]; 9] = [
+    [0.267004, 0.004874, 0.329415],
+    [0.278826, 0.175490, 0.483397],
+    [0.229739, 0.322361, 0.545706],
+    [0.172719, 0.448791, 0.557885],
+    [0.127568, 0.566949, 0.550556],
+    [0.157851, 0.683765, 0.501686],
+    [0.369214, 0.788888, 0.382914],
+    [0.678489, 0.863742, 0.189503],
+    [0.993248, 0.906157, 0.143936],
+];
+
+// Evenly spaced stops sampled from matplotlib's magma colormap.
+const MAGMA: [[f32; 3]; 9] = [
+    [0.001462, 0.000466, 0.013866],
+    [0.078815, 0.054184, 0.211667],
+    [0.232077, 0.059889, 0.437695],
+    [0.390384, 0.100379, 0.501864],
+    [0.550287, 0.161158, 0.505719],
+    [0.716387, 0.214982, 0.475290],
+    [0.868793, 0.287728, 0.409303],
+    [0.967671, 0.439703, 0.359810],
+    [0.987053, 0.991438, 0.749504],
+];
+
+impl ColormapReshaper {
+    /// Construct a new `ColormapReshaper`.
+    pub fn new(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+        colormap: Colormap,
+    ) -> Self {
+        // Create the sampler for sampling from the source texture. 32-bit float formats are not
+        // filterable on all backends, so sample the nearest texel.
+        let sampler = wgpu::SamplerBuilder::new()
+            .mag_filter(wgpu::FilterMode::Nearest)
+            .min_filter(wgpu::FilterMode::Nearest)
+            .build(device);
+
+        // Create the render pipeline.
+        let bind_group_layout = bind_group_layout(device);
+        let fs = include_bytes!("shaders/frag.spv");
+        let quad = FullscreenQuad::new(
+            device,
+            &bind_group_layout,
+            &fs[..],
+            dst_sample_count,
+            dst_format,
+        );
+
+        // Create the uniform buffer for the colormap.
+        let uniforms = Uniforms::new(colormap);
+        let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
+        let uniform_buffer = device
+            .create_buffer_mapped(1, usage)
+            .fill_from_slice(&[uniforms]);
+
+        // Create the bind group.
+        let bind_group = bind_group(
+            device,
+            &bind_group_layout,
+            src_texture,
+            &sampler,
+            &uniform_buffer,
+        );
+
+        ColormapReshaper {
+            bind_group_layout,
+            bind_group,
+            quad,
+            sampler,
+            uniform_buffer,
+            colormap,
+        }
+    }
+
+    /// The colormap currently in use.
+    pub fn colormap(&self) -> Colormap {
+        self.colormap
+    }
+
+    /// Update the colormap used by subsequent render passes.
+    ///
+    /// The new value is uploaded to the GPU via a copy command encoded with the given encoder.
+    pub fn set_colormap(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        colormap: Colormap,
+    ) {
+        self.colormap = colormap;
+        let uniforms = Uniforms::new(colormap);
+        let size = std::mem::size_of::<Uniforms>() as wgpu::BufferAddress;
+        let new_uniform_buffer = device
+            .create_buffer_mapped(1, wgpu::BufferUsage::COPY_SRC)
+            .fill_from_slice(&[uniforms]);
+        encoder.copy_buffer_to_buffer(&new_uniform_buffer, 0, &self.uniform_buffer, 0, size);
+    }
+
+    /// Given an encoder, submits a render pass command for writing the color mapped source
+    /// texture to the destination texture.
+    pub fn encode_render_pass(
+        &self,
+        dst_texture: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        self.quad
+            .encode_render_pass(dst_texture, &self.bind_group, encoder);
+    }
+}
+
+impl Colormap {
+    /// Map a single scalar value to an RGB color.
+    ///
+    /// The value is clamped to the range `0.0..=1.0`.
+    pub fn map(&self, x: f32) -> [f32; 3] {
+        let x = x.max(0.0).min(1.0);
+        match *self {
+            Colormap::Viridis => lerp_stops(&VIRIDIS, x),
+            Colormap::Magma => lerp_stops(&MAGMA, x),
+            Colormap::Jet => {
+                let channel = |offset: f32| (1.5 - (4.0 * x - offset).abs()).max(0.0).min(1.0);
+                [channel(3.0), channel(2.0), channel(1.0)]
+            }
+        }
+    }
+
+    // The value used to select the colormap within the fragment shader.
+    fn to_u32(&self) -> u32 {
+        match *self {
+            Colormap::Viridis => 0,
+            Colormap::Magma => 1,
+            Colormap::Jet => 2,
+        }
+    }
+}
+
+impl Uniforms {
+    fn new(colormap: Colormap) -> Self {
+        let colormap = colormap.to_u32();
+        Uniforms { colormap }
+    }
+}
+
+impl Default for Colormap {
+    fn default() -> Self {
+        Colormap::Viridis
+    }
+}
+
+fn bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    wgpu::BindGroupLayoutBuilder::new()
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+        )
+        .sampler(wgpu::ShaderStage::FRAGMENT)
+        .uniform_buffer(wgpu::ShaderStage::FRAGMENT, false)
+        .build(device)
+}
+
+fn bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    texture: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+    uniform_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    wgpu::BindGroupBuilder::new()
+        .texture_view(texture)
+        .sampler(sampler)
+        .buffer::<Uniforms>(uniform_buffer, 0..1)
+        .build(device, layout)
+}
+
+// Linearly interpolate between the evenly spaced stops at the given position within `0.0..=1.0`.
+fn lerp_stops(stops: &[[f32; 3]], x: f32) -> [f32; 3] {
+    let segments = stops.len() - 1;
+    let pos = x * segments as f32;
+    let i = std::cmp::min(pos as usize, segments - 1);
+    let t = pos - i as f32;
+    let [r0, g0, b0] = stops[i];
+    let [r1, g1, b1] = stops[i + 1];
+    [r0 + (r1 - r0) * t, g0 + (g1 - g0) * t, b0 + (b1 - b0) * t]
+}
@@ -0,0 +1,65 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag.spv shader.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+layout(set = 0, binding = 2) uniform Data {
+    uint colormap;
+} uniforms;
+
+// Evenly spaced stops matching `Colormap::Viridis`.
+const vec3 VIRIDIS[9] = vec3[9](
+    vec3(0.267004, 0.004874, 0.329415),
+    vec3(0.278826, 0.175490, 0.483397),
+    vec3(0.229739, 0.322361, 0.545706),
+    vec3(0.172719, 0.448791, 0.557885),
+    vec3(0.127568, 0.566949, 0.550556),
+    vec3(0.157851, 0.683765, 0.501686),
+    vec3(0.369214, 0.788888, 0.382914),
+    vec3(0.678489, 0.863742, 0.189503),
+    vec3(0.993248, 0.906157, 0.143936)
+);
+
+// Evenly spaced stops matching `Colormap::Magma`.
+const vec3 MAGMA[9] = vec3[9](
+    vec3(0.001462, 0.000466, 0.013866),
+    vec3(0.078815, 0.054184, 0.211667),
+    vec3(0.232077, 0.059889, 0.437695),
+    vec3(0.390384, 0.100379, 0.501864),
+    vec3(0.550287, 0.161158, 0.505719),
+    vec3(0.716387, 0.214982, 0.475290),
+    vec3(0.868793, 0.287728, 0.409303),
+    vec3(0.967671, 0.439703, 0.359810),
+    vec3(0.987053, 0.991438, 0.749504)
+);
+
+// Matches `Colormap::Jet`.
+vec3 jet(float x) {
+    vec3 y = vec3(1.5) - abs(vec3(4.0 * x) - vec3(3.0, 2.0, 1.0));
+    return clamp(y, 0.0, 1.0);
+}
+
+void main() {
+    float x = texture(sampler2D(tex, tex_sampler), tex_coords).r;
+    x = clamp(x, 0.0, 1.0);
+    float pos = x * 8.0;
+    int i = min(int(pos), 7);
+    float t = pos - float(i);
+    vec3 rgb;
+    if (uniforms.colormap == 0) {
+        rgb = mix(VIRIDIS[i], VIRIDIS[i + 1], t);
+    } else if (uniforms.colormap == 1) {
+        rgb = mix(MAGMA[i], MAGMA[i + 1], t);
+    } else {
+        rgb = jet(x);
+    }
+    f_color = vec4(rgb, 1.0);
+}
@@ -0,0 +1,205 @@
+use super::quad::FullscreenQuad;
+use crate::wgpu;
+
+/// Composites an overlay texture over a base texture using a selectable blend mode, writing the
+/// result to a destination texture.
+///
+/// This is useful for layer compositing, similar to the blend modes found in image editors. The
+/// overlay is blended with the base according to the `CompositeMode` and then mixed over the base
+/// by the overlay's alpha.
+///
+/// The base and overlay textures must have the `TextureUsage::SAMPLED` enabled and must not be
+/// multisampled. Multisampled textures should first be resolved. Both are sampled across their
+/// full extent, so they are typically the same size as the destination.
+///
+/// The destination texture must have the `TextureUsage::OUTPUT_ATTACHMENT` enabled and must not
+/// be either of the source textures.
+#[derive(Debug)]
+pub struct Compositor {
+    bind_group_layout: wgpu::BindGroupLayout,
+    quad: FullscreenQuad,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+}
+
+/// The blend equation used to combine the overlay color with the base color.
+///
+/// Each mode is applied per color channel to non-premultiplied colors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CompositeMode {
+    /// The overlay color replaces the base color.
+    Normal,
+    /// The colors are multiplied, darkening the base.
+    Multiply,
+    /// The inverse colors are multiplied and inverted, brightening the base.
+    Screen,
+    /// `Multiply` where the base is dark and `Screen` where the base is light, increasing
+    /// contrast.
+    Overlay,
+    /// The colors are summed, clamping at `1.0`.
+    Add,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Uniforms {
+    mode: u32,
+}
+
+impl Compositor {
+    /// Construct a new `Compositor` for writing to destination textures of the given sample count
+    /// and format.
+    pub fn new(
+        device: &wgpu::Device,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+    ) -> Self {
+        // Create the sampler for sampling from the source textures.
+        let sampler = wgpu::SamplerBuilder::new().build(device);
+
+        // Create the render pipeline.
+        let bind_group_layout = bind_group_layout(device);
+        let fs = include_bytes!("shaders/frag.spv");
+        let quad = FullscreenQuad::new(
+            device,
+            &bind_group_layout,
+            &fs[..],
+            dst_sample_count,
+            dst_format,
+        );
+
+        // Create the uniform buffer for the mode, updated on each composite.
+        let uniforms = Uniforms::new(CompositeMode::default());
+        let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
+        let uniform_buffer = device
+            .create_buffer_mapped(1, usage)
+            .fill_from_slice(&[uniforms]);
+
+        Compositor {
+            bind_group_layout,
+            quad,
+            sampler,
+            uniform_buffer,
+        }
+    }
+
+    /// Given an encoder, submits the commands for compositing the `overlay_view` over the
+    /// `base_view` with the given `mode` and writing the result to the `dst_view`.
+    ///
+    /// The entire destination is overwritten.
+    pub fn composite(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        base_view: &wgpu::TextureView,
+        overlay_view: &wgpu::TextureView,
+        dst_view: &wgpu::TextureView,
+        mode: CompositeMode,
+    ) {
+        // Upload the mode.
+        let uniforms = Uniforms::new(mode);
+        let size = std::mem::size_of::<Uniforms>() as wgpu::BufferAddress;
+        let new_uniform_buffer = device
+            .create_buffer_mapped(1, wgpu::BufferUsage::COPY_SRC)
+            .fill_from_slice(&[uniforms]);
+        encoder.copy_buffer_to_buffer(&new_uniform_buffer, 0, &self.uniform_buffer, 0, size);
+
+        let bind_group = bind_group(
+            device,
+            &self.bind_group_layout,
+            base_view,
+            overlay_view,
+            &self.sampler,
+            &self.uniform_buffer,
+        );
+        self.quad.encode_render_pass(dst_view, &bind_group, encoder);
+    }
+}
+
+impl CompositeMode {
+    /// Composite a single non-premultiplied RGBA `overlay` color over the given `base` color.
+    pub fn composite(&self, base: [f32; 4], overlay: [f32; 4]) -> [f32; 4] {
+        let [br, bg, bb, ba] = base;
+        let [or, og, ob, oa] = overlay;
+        let channel = |b: f32, o: f32| {
+            let blended = self.blend(b, o);
+            b + (blended - b) * oa
+        };
+        let a = oa + ba * (1.0 - oa);
+        [channel(br, or), channel(bg, og), channel(bb, ob), a]
+    }
+
+    /// Apply the blend equation to a single base and overlay color channel value.
+    pub fn blend(&self, b: f32, o: f32) -> f32 {
+        match *self {
+            CompositeMode::Normal => o,
+            CompositeMode::Multiply => b * o,
+            CompositeMode::Screen => 1.0 - (1.0 - b) * (1.0 - o),
+            CompositeMode::Overlay => {
+                if b < 0.5 {
+                    2.0 * b * o
+                } else {
+                    1.0 - 2.0 * (1.0 - b) * (1.0 - o)
+                }
+            }
+            CompositeMode::Add => (b + o).min(1.0),
+        }
+    }
+
+    // The value used to select the mode within the fragment shader.
+    fn to_u32(&self) -> u32 {
+        match *self {
+            CompositeMode::Normal => 0,
+            CompositeMode::Multiply => 1,
+            CompositeMode::Screen => 2,
+            CompositeMode::Overlay => 3,
+            CompositeMode::Add => 4,
+        }
+    }
+}
+
+impl Uniforms {
+    fn new(mode: CompositeMode) -> Self {
+        let mode = mode.to_u32();
+        Uniforms { mode }
+    }
+}
+
+impl Default for CompositeMode {
+    fn default() -> Self {
+        CompositeMode::Normal
+    }
+}
+
+fn bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    wgpu::BindGroupLayoutBuilder::new()
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+        )
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+        )
+        .sampler(wgpu::ShaderStage::FRAGMENT)
+        .uniform_buffer(wgpu::ShaderStage::FRAGMENT, false)
+        .build(device)
+}
+
+fn bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    base: &wgpu::TextureView,
+    overlay: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+    uniform_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    wgpu::BindGroupBuilder::new()
+        .texture_view(base)
+        .texture_view(overlay)
+        .sampler(sampler)
+        .buffer::<Uniforms>(uniform_buffer, 0..1)
+        .build(device, layout)
+}
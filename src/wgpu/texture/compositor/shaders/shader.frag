@@ -0,0 +1,47 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag.spv shader.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D base_tex;
+layout(set = 0, binding = 1) uniform texture2D overlay_tex;
+layout(set = 0, binding = 2) uniform sampler tex_sampler;
+layout(set = 0, binding = 3) uniform Data {
+    uint mode;
+} uniforms;
+
+// Matches `CompositeMode::to_u32`.
+const uint MODE_NORMAL = 0;
+const uint MODE_MULTIPLY = 1;
+const uint MODE_SCREEN = 2;
+const uint MODE_OVERLAY = 3;
+const uint MODE_ADD = 4;
+
+vec3 blend(vec3 b, vec3 o) {
+    if (uniforms.mode == MODE_MULTIPLY) {
+        return b * o;
+    } else if (uniforms.mode == MODE_SCREEN) {
+        return 1.0 - (1.0 - b) * (1.0 - o);
+    } else if (uniforms.mode == MODE_OVERLAY) {
+        vec3 lo = 2.0 * b * o;
+        vec3 hi = 1.0 - 2.0 * (1.0 - b) * (1.0 - o);
+        return mix(hi, lo, lessThan(b, vec3(0.5)));
+    } else if (uniforms.mode == MODE_ADD) {
+        return min(b + o, vec3(1.0));
+    }
+    return o;
+}
+
+void main() {
+    vec4 base = texture(sampler2D(base_tex, tex_sampler), tex_coords);
+    vec4 overlay = texture(sampler2D(overlay_tex, tex_sampler), tex_coords);
+    vec3 rgb = mix(base.rgb, blend(base.rgb, overlay.rgb), overlay.a);
+    float a = overlay.a + base.a * (1.0 - overlay.a);
+    f_color = vec4(rgb, a);
+}
@@ -0,0 +1,109 @@
+use super::quad::FullscreenQuad;
+use crate::wgpu;
+
+/// Writes a texture to another texture while applying fast approximate anti-aliasing (FXAA).
+///
+/// FXAA smooths high contrast edges in a single fragment pass over a resolved image. It is
+/// useful as a fallback where hardware multisampling is unavailable or too costly.
+///
+/// The `src_texture` must have the `TextureUsage::SAMPLED` enabled and must not be multisampled.
+/// Multisampled textures should first be resolved.
+///
+/// The `dst_texture` must have the `TextureUsage::OUTPUT_ATTACHMENT` enabled.
+///
+/// The `REDUCE_MIN`, `REDUCE_MUL` and `SPAN_MAX` constants are read-only mirrors of the values
+/// hard-coded within the fragment shader. They are provided for reference and changing them has no
+/// effect on the pass.
+#[derive(Debug)]
+pub struct FxaaReshaper {
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    quad: FullscreenQuad,
+    sampler: wgpu::Sampler,
+}
+
+impl FxaaReshaper {
+    /// The minimum amount by which the edge direction is reduced, avoiding division by zero in
+    /// flat regions.
+    pub const REDUCE_MIN: f32 = 1.0 / 128.0;
+    /// Scales the reduction of the edge direction by the average luma of the neighbourhood.
+    pub const REDUCE_MUL: f32 = 1.0 / 8.0;
+    /// The maximum distance in pixels along the edge over which colors are blended.
+    pub const SPAN_MAX: f32 = 8.0;
+
+    /// Construct a new `FxaaReshaper`.
+    pub fn new(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+    ) -> Self {
+        // Create the sampler for sampling from the source texture. FXAA relies on linear
+        // filtering to blend between neighbouring pixels.
+        let sampler = wgpu::SamplerBuilder::new()
+            .mag_filter(wgpu::FilterMode::Linear)
+            .min_filter(wgpu::FilterMode::Linear)
+            .build(device);
+
+        // Create the render pipeline.
+        let bind_group_layout = bind_group_layout(device);
+        let fs = include_bytes!("shaders/frag.spv");
+        let quad = FullscreenQuad::new(
+            device,
+            &bind_group_layout,
+            &fs[..],
+            dst_sample_count,
+            dst_format,
+        );
+
+        // Create the bind group.
+        let bind_group = bind_group(device, &bind_group_layout, src_texture, &sampler);
+
+        FxaaReshaper {
+            bind_group_layout,
+            bind_group,
+            quad,
+            sampler,
+        }
+    }
+
+    /// The perceived brightness of the given color, used to detect edges.
+    pub fn luma(rgb: [f32; 3]) -> f32 {
+        let [r, g, b] = rgb;
+        r * 0.299 + g * 0.587 + b * 0.114
+    }
+
+    /// Given an encoder, submits a render pass command for writing the anti-aliased source
+    /// texture to the destination texture.
+    pub fn encode_render_pass(
+        &self,
+        dst_texture: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        self.quad
+            .encode_render_pass(dst_texture, &self.bind_group, encoder);
+    }
+}
+
+fn bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    wgpu::BindGroupLayoutBuilder::new()
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+        )
+        .sampler(wgpu::ShaderStage::FRAGMENT)
+        .build(device)
+}
+
+fn bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    texture: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    wgpu::BindGroupBuilder::new()
+        .texture_view(texture)
+        .sampler(sampler)
+        .build(device, layout)
+}
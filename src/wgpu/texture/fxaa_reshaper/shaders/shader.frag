@@ -0,0 +1,67 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag.spv shader.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+
+// Matches the constants on `FxaaReshaper`.
+const float REDUCE_MIN = 1.0 / 128.0;
+const float REDUCE_MUL = 1.0 / 8.0;
+const float SPAN_MAX = 8.0;
+
+// Matches `FxaaReshaper::luma`.
+float luma(vec3 rgb) {
+    return dot(rgb, vec3(0.299, 0.587, 0.114));
+}
+
+vec4 sample_tex(vec2 uv) {
+    return texture(sampler2D(tex, tex_sampler), uv);
+}
+
+void main() {
+    vec2 inv_size = 1.0 / vec2(textureSize(sampler2D(tex, tex_sampler), 0));
+
+    // Sample the luma of the diagonal neighbours and the centre.
+    vec4 center = sample_tex(tex_coords);
+    float luma_nw = luma(sample_tex(tex_coords + vec2(-1.0, -1.0) * inv_size).rgb);
+    float luma_ne = luma(sample_tex(tex_coords + vec2(1.0, -1.0) * inv_size).rgb);
+    float luma_sw = luma(sample_tex(tex_coords + vec2(-1.0, 1.0) * inv_size).rgb);
+    float luma_se = luma(sample_tex(tex_coords + vec2(1.0, 1.0) * inv_size).rgb);
+    float luma_m = luma(center.rgb);
+    float luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    float luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+    // Estimate the direction of the edge running through this pixel.
+    vec2 dir = vec2(
+        -((luma_nw + luma_ne) - (luma_sw + luma_se)),
+        (luma_nw + luma_sw) - (luma_ne + luma_se)
+    );
+    float luma_sum = luma_nw + luma_ne + luma_sw + luma_se;
+    float dir_reduce = max(luma_sum * 0.25 * REDUCE_MUL, REDUCE_MIN);
+    float rcp_dir_min = 1.0 / (min(abs(dir.x), abs(dir.y)) + dir_reduce);
+    dir = clamp(dir * rcp_dir_min, vec2(-SPAN_MAX), vec2(SPAN_MAX)) * inv_size;
+
+    // Blur along the edge, falling back to the narrower blur if the wider one overshoots.
+    vec3 rgb_a = 0.5 * (
+        sample_tex(tex_coords + dir * (1.0 / 3.0 - 0.5)).rgb +
+        sample_tex(tex_coords + dir * (2.0 / 3.0 - 0.5)).rgb
+    );
+    vec3 rgb_b = rgb_a * 0.5 + 0.25 * (
+        sample_tex(tex_coords + dir * -0.5).rgb +
+        sample_tex(tex_coords + dir * 0.5).rgb
+    );
+    float luma_b = luma(rgb_b);
+    if (luma_b < luma_min || luma_b > luma_max) {
+        f_color = vec4(rgb_a, center.a);
+    } else {
+        f_color = vec4(rgb_b, center.a);
+    }
+}
@@ -1,9 +1,15 @@
 use crate::wgpu::{self, TextureHandle};
+use std::fmt;
 use std::ops::Deref;
 
 pub mod capturer;
+pub mod colormap_reshaper;
+pub mod compositor;
+pub mod fxaa_reshaper;
 pub mod image;
+mod quad;
 pub mod reshaper;
+pub mod tone_map_reshaper;
 
 /// A convenient wrapper around a handle to a texture on the GPU along with its descriptor.
 ///
@@ -27,6 +33,19 @@ pub struct Builder {
     descriptor: wgpu::TextureDescriptor,
 }
 
+/// Errors that might occur while creating a **Texture**.
+#[derive(Debug)]
+pub enum Error {
+    /// The length of the given data did not match the size and format of the texture.
+    DataSizeMismatch { expected: usize, actual: usize },
+    /// The requested region does not lie within the bounds of the texture.
+    RegionOutOfBounds {
+        origin: [u32; 2],
+        size: [u32; 2],
+        texture_size: [u32; 2],
+    },
+}
+
 /// A wrapper around a `wgpu::Buffer` containing bytes of a known length.
 #[derive(Debug)]
 pub struct BufferBytes {
@@ -248,6 +267,161 @@ impl Texture {
         desc
     }
 
+    /// Create a texture that may be both rendered to and sampled from.
+    ///
+    /// The texture is created with the `OUTPUT_ATTACHMENT`, `SAMPLED` and `COPY_SRC` usages. This
+    /// is the common case for intermediary targets within post-processing chains.
+    pub fn render_target(
+        device: &wgpu::Device,
+        size: [u32; 2],
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        wgpu::TextureBuilder::new()
+            .size(size)
+            .format(format)
+            .sample_count(sample_count)
+            .usage(Builder::RENDER_TARGET_USAGE)
+            .build(device)
+    }
+
+    /// Load a texture directly from a slice of raw pixel data using the given device queue.
+    ///
+    /// The `data` is expected to be tightly packed, row by row, in the given `format`. Rows are
+    /// padded as necessary during the upload.
+    ///
+    /// Returns an error if the length of `data` does not match `size` and `format`.
+    pub fn from_bytes(
+        device: &wgpu::Device,
+        queue: &mut wgpu::Queue,
+        size: [u32; 2],
+        format: wgpu::TextureFormat,
+        data: &[u8],
+    ) -> Result<Self, Error> {
+        let [width, height] = size;
+        let row_len_bytes = width as usize * format_size_bytes(format) as usize;
+        let expected = row_len_bytes * height as usize;
+        if data.len() != expected {
+            let actual = data.len();
+            return Err(Error::DataSizeMismatch { expected, actual });
+        }
+
+        let texture = wgpu::TextureBuilder::new()
+            .size(size)
+            .format(format)
+            .usage(wgpu::TextureUsage::COPY_DST | wgpu::TextureUsage::SAMPLED)
+            .build(device);
+
+        // Pad each row to the alignment required for buffer to texture copies.
+        let row_pitch = padded_row_len_bytes(row_len_bytes);
+        let mut padded = vec![0u8; row_pitch * height as usize];
+        if row_len_bytes > 0 {
+            let src_rows = data.chunks(row_len_bytes);
+            for (dst, src) in padded.chunks_mut(row_pitch).zip(src_rows) {
+                dst[..row_len_bytes].copy_from_slice(src);
+            }
+        }
+        let buffer = device
+            .create_buffer_mapped(padded.len(), wgpu::BufferUsage::COPY_SRC)
+            .fill_from_slice(&padded);
+
+        let cmd_encoder_desc = wgpu::CommandEncoderDescriptor::default();
+        let mut encoder = device.create_command_encoder(&cmd_encoder_desc);
+        let buffer_copy_view = wgpu::BufferCopyView {
+            buffer: &buffer,
+            offset: 0,
+            row_pitch: row_pitch as u32,
+            image_height: height,
+        };
+        let texture_copy_view = texture.create_default_copy_view();
+        let extent = texture.extent();
+        encoder.copy_buffer_to_texture(buffer_copy_view, texture_copy_view, extent);
+        queue.submit(&[encoder.finish()]);
+
+        Ok(texture)
+    }
+
+    /// Read the pixels within the given region of the texture back to host memory.
+    ///
+    /// Only the requested region is copied from the texture, making this much cheaper than reading
+    /// the whole texture when only a small crop is required. The returned bytes are tightly
+    /// packed, row by row, in the texture's format. This blocks until the GPU has completed the
+    /// copy.
+    ///
+    /// The texture must have been created with the `COPY_SRC` usage and must not be multisampled.
+    ///
+    /// Returns an error if the region does not lie within the bounds of the texture.
+    pub fn read_region(
+        &self,
+        device: &wgpu::Device,
+        queue: &mut wgpu::Queue,
+        origin: [u32; 2],
+        size: [u32; 2],
+    ) -> Result<Vec<u8>, Error> {
+        let texture_size = self.size();
+        let in_bounds = (0..2).all(|i| origin[i] as u64 + size[i] as u64 <= texture_size[i] as u64);
+        if !in_bounds {
+            return Err(Error::RegionOutOfBounds {
+                origin,
+                size,
+                texture_size,
+            });
+        }
+
+        // Rows copied to the buffer must be padded to the required alignment.
+        let [width, height] = size;
+        let row_len_bytes = width as usize * format_size_bytes(self.format()) as usize;
+        let row_pitch = padded_row_len_bytes(row_len_bytes);
+        let buffer_descriptor = wgpu::BufferDescriptor {
+            size: (row_pitch * height as usize) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        };
+        let buffer = device.create_buffer(&buffer_descriptor);
+
+        // Copy only the requested region.
+        let cmd_encoder_desc = wgpu::CommandEncoderDescriptor::default();
+        let mut encoder = device.create_command_encoder(&cmd_encoder_desc);
+        let [x, y] = origin;
+        let texture_copy_view = wgpu::TextureCopyView {
+            texture: &self.texture,
+            mip_level: 0,
+            array_layer: 0,
+            origin: wgpu::Origin3d {
+                x: x as f32,
+                y: y as f32,
+                z: 0.0,
+            },
+        };
+        let buffer_copy_view = wgpu::BufferCopyView {
+            buffer: &buffer,
+            offset: 0,
+            row_pitch: row_pitch as u32,
+            image_height: height,
+        };
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+        encoder.copy_texture_to_buffer(texture_copy_view, buffer_copy_view, extent);
+        queue.submit(&[encoder.finish()]);
+
+        // Strip the row padding while reading the mapped buffer.
+        let bytes = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let bytes2 = bytes.clone();
+        buffer.map_read_async(0, buffer_descriptor.size, move |result| {
+            let mapping = result.expect("failed to map texture region buffer");
+            let mut bytes = bytes2.borrow_mut();
+            if row_len_bytes > 0 {
+                for row in mapping.data.chunks(row_pitch) {
+                    bytes.extend_from_slice(&row[..row_len_bytes]);
+                }
+            }
+        });
+        device.poll(true);
+        Ok(bytes.replace(vec![]))
+    }
+
     /// Creates a `TextureCopyView` ready for copying to or from the entire texture.
     pub fn create_default_copy_view(&self) -> wgpu::TextureCopyView {
         wgpu::TextureCopyView {
@@ -289,6 +463,11 @@ impl Builder {
     pub const DEFAULT_DIMENSION: wgpu::TextureDimension = wgpu::TextureDimension::D2;
     pub const DEFAULT_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
     pub const DEFAULT_USAGE: wgpu::TextureUsage = wgpu::TextureUsage::ORDERED;
+    pub const RENDER_TARGET_USAGE: wgpu::TextureUsage = wgpu::TextureUsage::from_bits_truncate(
+        wgpu::TextureUsage::OUTPUT_ATTACHMENT.bits()
+            | wgpu::TextureUsage::SAMPLED.bits()
+            | wgpu::TextureUsage::COPY_SRC.bits(),
+    );
     pub const DEFAULT_DESCRIPTOR: wgpu::TextureDescriptor = wgpu::TextureDescriptor {
         size: Self::DEFAULT_SIZE,
         array_layer_count: Self::DEFAULT_ARRAY_LAYER_COUNT,
@@ -458,6 +637,29 @@ impl Into<wgpu::TextureDescriptor> for Builder {
     }
 }
 
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::DataSizeMismatch { expected, actual } => write!(
+                f,
+                "expected {} bytes of texture data but {} were given",
+                expected, actual,
+            ),
+            Error::RegionOutOfBounds {
+                origin,
+                size,
+                texture_size,
+            } => write!(
+                f,
+                "region at {:?} of size {:?} exceeds the bounds of the {:?} texture",
+                origin, size, texture_size,
+            ),
+        }
+    }
+}
+
 /// Return the size of the given texture format in bytes.
 pub fn format_size_bytes(format: wgpu::TextureFormat) -> u32 {
     use crate::wgpu::TextureFormat::*;
@@ -474,6 +676,17 @@ pub fn format_size_bytes(format: wgpu::TextureFormat) -> u32 {
     }
 }
 
+/// Returns `true` if the given texture format is non-linear sRGB.
+///
+/// Sampling from a texture of an sRGB format decodes its colors to linear and writing to an
+/// attachment of an sRGB format encodes linear colors to sRGB.
+pub fn format_is_srgb(format: wgpu::TextureFormat) -> bool {
+    match format {
+        wgpu::TextureFormat::Rgba8UnormSrgb | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+        _ => false,
+    }
+}
+
 /// Returns `true` if the given `wgpu::Extent3d`s are equal.
 pub fn extent_3d_eq(a: &wgpu::Extent3d, b: &wgpu::Extent3d) -> bool {
     a.width == b.width && a.height == b.height && a.depth == b.depth
@@ -489,3 +702,15 @@ pub fn descriptor_eq(a: &wgpu::TextureDescriptor, b: &wgpu::TextureDescriptor) -
         && a.format == b.format
         && a.usage == b.usage
 }
+
+// The alignment in bytes required of each row of a buffer used in a buffer to texture copy.
+const ROW_PITCH_ALIGNMENT: usize = 256;
+
+// Round the given row length up to the nearest multiple of `ROW_PITCH_ALIGNMENT`.
+fn padded_row_len_bytes(row_len_bytes: usize) -> usize {
+    let rem = row_len_bytes % ROW_PITCH_ALIGNMENT;
+    match rem {
+        0 => row_len_bytes,
+        _ => row_len_bytes + ROW_PITCH_ALIGNMENT - rem,
+    }
+}
@@ -0,0 +1,151 @@
+use crate::wgpu;
+
+/// A render pipeline that writes a single fragment shader over the entire destination texture.
+///
+/// Shared by the texture passes that differ only by their fragment shader and bind group, e.g.
+/// the tone mapping, FXAA, compositing and colormap reshapers. The vertex shader is shared with
+/// the `Reshaper`.
+#[derive(Debug)]
+pub struct FullscreenQuad {
+    _vs_mod: wgpu::ShaderModule,
+    _fs_mod: wgpu::ShaderModule,
+    render_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+}
+
+/// The vertex type expected by the shared vertex shader.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Vertex {
+    pub position: [f32; 2],
+    pub tex_coords: [f32; 2],
+}
+
+impl FullscreenQuad {
+    /// Construct the pipeline for the given fragment shader SPIR-V and bind group layout.
+    pub fn new(
+        device: &wgpu::Device,
+        bind_group_layout: &wgpu::BindGroupLayout,
+        fs_spirv: &[u8],
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+    ) -> Self {
+        // Load shader modules.
+        let vs = include_bytes!("reshaper/shaders/vert.spv");
+        let vs_spirv = wgpu::read_spirv(std::io::Cursor::new(&vs[..]))
+            .expect("failed to read hard-coded SPIRV");
+        let vs_mod = device.create_shader_module(&vs_spirv);
+        let fs_spirv = wgpu::read_spirv(std::io::Cursor::new(fs_spirv))
+            .expect("failed to read hard-coded SPIRV");
+        let fs_mod = device.create_shader_module(&fs_spirv);
+
+        // Create the render pipeline.
+        let pipeline_layout = pipeline_layout(device, bind_group_layout);
+        let render_pipeline = render_pipeline(
+            device,
+            &pipeline_layout,
+            &vs_mod,
+            &fs_mod,
+            dst_sample_count,
+            dst_format,
+        );
+
+        // Create the vertex buffer.
+        let vertex_buffer = device
+            .create_buffer_mapped(VERTICES.len(), wgpu::BufferUsage::VERTEX)
+            .fill_from_slice(&VERTICES[..]);
+
+        FullscreenQuad {
+            _vs_mod: vs_mod,
+            _fs_mod: fs_mod,
+            render_pipeline,
+            vertex_buffer,
+        }
+    }
+
+    /// Given an encoder, submits a render pass command for writing the quad to the entire
+    /// destination texture with the given bind group.
+    pub fn encode_render_pass(
+        &self,
+        dst_texture: &wgpu::TextureView,
+        bind_group: &wgpu::BindGroup,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(dst_texture, |color| color)
+            .begin(encoder);
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffers(0, &[(&self.vertex_buffer, 0)]);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        let vertex_range = 0..VERTICES.len() as u32;
+        let instance_range = 0..1;
+        render_pass.draw(vertex_range, instance_range);
+    }
+}
+
+impl wgpu::VertexDescriptor for Vertex {
+    const STRIDE: wgpu::BufferAddress = std::mem::size_of::<Vertex>() as _;
+    const ATTRIBUTES: &'static [wgpu::VertexAttributeDescriptor] = &[
+        // position
+        wgpu::VertexAttributeDescriptor {
+            format: wgpu::VertexFormat::Float2,
+            offset: 0,
+            shader_location: 0,
+        },
+        // tex_coords
+        wgpu::VertexAttributeDescriptor {
+            format: wgpu::VertexFormat::Float2,
+            offset: std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+            shader_location: 1,
+        },
+    ];
+}
+
+const VERTICES: [Vertex; 4] = [
+    Vertex {
+        position: [-1.0, -1.0],
+        tex_coords: [0.0, 0.0],
+    },
+    Vertex {
+        position: [-1.0, 1.0],
+        tex_coords: [0.0, 1.0],
+    },
+    Vertex {
+        position: [1.0, -1.0],
+        tex_coords: [1.0, 0.0],
+    },
+    Vertex {
+        position: [1.0, 1.0],
+        tex_coords: [1.0, 1.0],
+    },
+];
+
+fn pipeline_layout(
+    device: &wgpu::Device,
+    bind_group_layout: &wgpu::BindGroupLayout,
+) -> wgpu::PipelineLayout {
+    let desc = wgpu::PipelineLayoutDescriptor {
+        bind_group_layouts: &[&bind_group_layout],
+    };
+    device.create_pipeline_layout(&desc)
+}
+
+fn render_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    vs_mod: &wgpu::ShaderModule,
+    fs_mod: &wgpu::ShaderModule,
+    dst_sample_count: u32,
+    dst_format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    wgpu::RenderPipelineBuilder::from_layout(layout, vs_mod)
+        .fragment_shader(fs_mod)
+        .color_format(dst_format)
+        .color_blend(wgpu::BlendDescriptor::REPLACE)
+        .alpha_blend(wgpu::BlendDescriptor::REPLACE)
+        .add_vertex_buffer::<Vertex>()
+        .primitive_topology(wgpu::PrimitiveTopology::TriangleStrip)
+        .index_format(wgpu::IndexFormat::Uint16)
+        .sample_count(dst_sample_count)
+        .build(device)
+}
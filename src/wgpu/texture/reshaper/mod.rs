@@ -1,3 +1,6 @@
+use super::quad::Vertex;
+use crate::geom;
+use crate::math::{cgmath, Matrix3};
 use crate::wgpu;
 
 /// Reshapes a texture from its original size, sample_count and format to the destination size,
@@ -6,6 +9,12 @@ use crate::wgpu;
 /// The `src_texture` must have the `TextureUsage::SAMPLED` enabled.
 ///
 /// The `dst_texture` must have the `TextureUsage::OUTPUT_ATTACHMENT` enabled.
+///
+/// The source is sampled and written without any explicit color conversion, so any sRGB
+/// conversion is determined by the source and destination formats. See `SrgbConversion`.
+///
+/// A `Builder` may be used to select the filter mode used for scaling, to apply an explicit
+/// `ColorConversion` or to reshape individual layers of `D2Array` source texture views.
 #[derive(Debug)]
 pub struct Reshaper {
     _vs_mod: wgpu::ShaderModule,
@@ -16,12 +25,74 @@ pub struct Reshaper {
     sampler: wgpu::Sampler,
     uniform_buffer: Option<wgpu::Buffer>,
     vertex_buffer: wgpu::Buffer,
+    transformed_vertex_buffer: wgpu::Buffer,
+    view_dimension: wgpu::TextureViewDimension,
+    src_rect: geom::Rect<f32>,
 }
 
-#[repr(C)]
-#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
-struct Vertex {
-    pub position: [f32; 2],
+/// A builder type for constructing a `Reshaper` with non-default parameters.
+///
+/// By default the entire source texture is sampled as a `D2` view with linear filtering.
+#[derive(Debug)]
+pub struct Builder {
+    src_sample_count: u32,
+    dst_sample_count: u32,
+    dst_format: wgpu::TextureFormat,
+    src_rect: geom::Rect<f32>,
+    view_dimension: wgpu::TextureViewDimension,
+    layer: u32,
+    filter_mode: wgpu::FilterMode,
+    color_conversion: ColorConversion,
+}
+
+/// Describes one half of a side-by-side stereo texture.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Eye {
+    /// The left half of the texture.
+    Left,
+    /// The right half of the texture.
+    Right,
+}
+
+/// The net sRGB conversion implicitly applied to colors when reshaping between two formats.
+///
+/// Sampling from an sRGB texture decodes to linear while writing to an sRGB attachment encodes
+/// from linear. When both formats are sRGB, these cancel out and the colors are copied as is. When
+/// only one side is sRGB, the stored values change, which is a common source of washed out or
+/// overly dark results when the data is not intended to be converted.
+///
+/// This is a standalone helper for diagnosing such results. The `Reshaper` is not told the source
+/// format and so does not check it. To undo an unwanted conversion, apply the inverse
+/// `ColorConversion` via `Builder::color_conversion`, i.e. `LinearToSrgb` for `Decode` or
+/// `SrgbToLinear` for `Encode`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SrgbConversion {
+    /// The stored values are copied unchanged.
+    None,
+    /// The stored values are decoded from sRGB to linear, i.e. only the source is sRGB.
+    Decode,
+    /// The stored values are encoded from linear to sRGB, i.e. only the destination is sRGB.
+    Encode,
+}
+
+/// An explicit color space conversion applied to the source colors while reshaping.
+///
+/// Unlike `SrgbConversion`, which describes the conversion implied by the source and destination
+/// formats, this conversion is applied by the fragment shader regardless of format. This is useful
+/// when the stored values do not match the color space implied by their format, e.g. when writing
+/// linear colors to a non-sRGB `Rgba8Unorm` texture that is to be displayed as sRGB. Take care not
+/// to convert twice when the formats already imply the same conversion, see
+/// `SrgbConversion::between`.
+///
+/// Colors are clamped to the `0.0..=1.0` range before being converted and alpha is unchanged.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColorConversion {
+    /// The sampled colors are written as is.
+    None,
+    /// The sampled colors are encoded from linear to sRGB.
+    LinearToSrgb,
+    /// The sampled colors are decoded from sRGB to linear.
+    SrgbToLinear,
 }
 
 #[repr(C)]
@@ -30,6 +101,19 @@ struct Uniforms {
     sample_count: u32,
 }
 
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct LayerUniforms {
+    layer: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct ConversionUniforms {
+    sample_count: u32,
+    conversion: u32,
+}
+
 impl Reshaper {
     /// Construct a new `Reshaper`.
     pub fn new(
@@ -39,28 +123,336 @@ impl Reshaper {
         dst_sample_count: u32,
         dst_format: wgpu::TextureFormat,
     ) -> Self {
+        let src_rect = full_src_rect();
+        Self::with_src_rect(
+            device,
+            src_texture,
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            src_rect,
+        )
+    }
+
+    /// Construct a `Reshaper` that writes only the given region of the source texture to the
+    /// entire destination texture.
+    ///
+    /// The `src_rect` is described in normalised texture coordinates, where `[0.0, 0.0]` and
+    /// `[1.0, 1.0]` are opposite corners of the source texture.
+    pub fn with_src_rect(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+        src_rect: geom::Rect<f32>,
+    ) -> Self {
+        Builder::new(src_sample_count, dst_sample_count, dst_format)
+            .src_rect(src_rect)
+            .build(device, src_texture)
+    }
+
+    /// Construct a `Reshaper` that writes one half of a side-by-side stereo source texture to the
+    /// entire destination texture.
+    pub fn stereo_half(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+        eye: Eye,
+    ) -> Self {
+        Self::with_src_rect(
+            device,
+            src_texture,
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            eye.src_rect(),
+        )
+    }
+
+    /// The dimension of the source texture view that the reshaper samples from.
+    pub fn view_dimension(&self) -> wgpu::TextureViewDimension {
+        self.view_dimension
+    }
+
+    /// Select the array layer of a `D2Array` source texture view that is written by subsequent
+    /// render passes.
+    ///
+    /// The new layer is uploaded to the GPU via a copy command encoded with the given encoder.
+    ///
+    /// **Panic!**s if the reshaper was not built for a `D2Array` source texture view.
+    pub fn set_layer(&self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder, layer: u32) {
+        assert_eq!(
+            self.view_dimension,
+            wgpu::TextureViewDimension::D2Array,
+            "only reshapers for `D2Array` source texture views have a layer",
+        );
+        let uniform_buffer = self
+            .uniform_buffer
+            .as_ref()
+            .expect("no layer uniform buffer");
+        let uniforms = LayerUniforms { layer };
+        let size = std::mem::size_of::<LayerUniforms>() as wgpu::BufferAddress;
+        let new_uniform_buffer = device
+            .create_buffer_mapped(1, wgpu::BufferUsage::COPY_SRC)
+            .fill_from_slice(&[uniforms]);
+        encoder.copy_buffer_to_buffer(&new_uniform_buffer, 0, uniform_buffer, 0, size);
+    }
+
+    /// Given an encoder, submits a render pass command for writing the source texture to the
+    /// destination texture.
+    pub fn encode_render_pass(
+        &self,
+        dst_texture: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(dst_texture, |color| color)
+            .begin(encoder);
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffers(0, &[(&self.vertex_buffer, 0)]);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        let vertex_range = 0..NUM_VERTICES as u32;
+        let instance_range = 0..1;
+        render_pass.draw(vertex_range, instance_range);
+    }
+
+    /// Given an encoder, submits a render pass command for writing the source texture to a region
+    /// of the destination texture, e.g. a cell within a grid of thumbnails.
+    ///
+    /// The `region` is in pixels where the lowest *y* value is the top row of the region, matching
+    /// the layout of texture data. The viewport and scissor rect are set to the region and the
+    /// existing contents of the destination are loaded, so the rest of the destination is left
+    /// untouched.
+    ///
+    /// **Panic!**s if the `region` does not lie within the destination of size `dst_size`.
+    pub fn encode_render_pass_to_region(
+        &self,
+        dst_texture: &wgpu::TextureView,
+        dst_size: [u32; 2],
+        region: geom::Rect<u32>,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let (x, y) = (region.left(), region.bottom());
+        let (w, h) = (region.right() - x, region.top() - y);
+        let fits =
+            |start: u32, len: u32, max: u32| start.checked_add(len).map_or(false, |end| end <= max);
+        assert!(
+            fits(x, w, dst_size[0]) && fits(y, h, dst_size[1]),
+            "region {:?} exceeds the bounds of the {:?} destination",
+            region,
+            dst_size,
+        );
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(dst_texture, |color| color.load_op(wgpu::LoadOp::Load))
+            .begin(encoder);
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_viewport(x as f32, y as f32, w as f32, h as f32, 0.0, 1.0);
+        render_pass.set_scissor_rect(x, y, w, h);
+        render_pass.set_vertex_buffers(0, &[(&self.vertex_buffer, 0)]);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        let vertex_range = 0..NUM_VERTICES as u32;
+        let instance_range = 0..1;
+        render_pass.draw(vertex_range, instance_range);
+    }
+
+    /// Given an encoder, submits a render pass command for writing the source texture to the
+    /// destination texture as a quad placed by the given affine `transform`, e.g. to draw a
+    /// texture as a rotated and scaled sprite.
+    ///
+    /// The `transform` is applied to the homogeneous `[x, y, 1.0]` positions of the quad that
+    /// would otherwise cover the destination, in normalised device coordinates where `[-1.0,
+    /// -1.0]` is the bottom-left corner and `[1.0, 1.0]` is the top-right. The existing contents
+    /// of the destination are loaded, so the area outside of the quad is left untouched. See
+    /// `transformed_quad` for the resulting corner positions.
+    ///
+    /// The transformed vertices are uploaded to the GPU via a copy command encoded with the given
+    /// encoder.
+    pub fn encode_render_pass_with_transform(
+        &self,
+        device: &wgpu::Device,
+        dst_texture: &wgpu::TextureView,
+        transform: Matrix3<f32>,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let vertices = transformed_vertices(self.src_rect, transform);
+        let size = std::mem::size_of_val(&vertices) as wgpu::BufferAddress;
+        let new_vertex_buffer = device
+            .create_buffer_mapped(vertices.len(), wgpu::BufferUsage::COPY_SRC)
+            .fill_from_slice(&vertices[..]);
+        encoder.copy_buffer_to_buffer(
+            &new_vertex_buffer,
+            0,
+            &self.transformed_vertex_buffer,
+            0,
+            size,
+        );
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(dst_texture, |color| color.load_op(wgpu::LoadOp::Load))
+            .begin(encoder);
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffers(0, &[(&self.transformed_vertex_buffer, 0)]);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        let vertex_range = 0..NUM_VERTICES as u32;
+        let instance_range = 0..1;
+        render_pass.draw(vertex_range, instance_range);
+    }
+
+    /// The corners of the quad written by `encode_render_pass_with_transform` for the given
+    /// `transform`, in normalised device coordinates.
+    ///
+    /// The corners are ordered bottom-left, top-left, bottom-right, top-right, i.e. the transformed
+    /// corners of the destination `[-1.0, -1.0]`, `[-1.0, 1.0]`, `[1.0, -1.0]` and `[1.0, 1.0]`.
+    pub fn transformed_quad(transform: Matrix3<f32>) -> [geom::Point2<f32>; NUM_VERTICES] {
+        let vertices = transformed_vertices(full_src_rect(), transform);
+        let corner = |v: &Vertex| geom::pt2(v.position[0], v.position[1]);
+        [
+            corner(&vertices[0]),
+            corner(&vertices[1]),
+            corner(&vertices[2]),
+            corner(&vertices[3]),
+        ]
+    }
+}
+
+impl Builder {
+    /// The source texture view dimension used by default.
+    pub const DEFAULT_VIEW_DIMENSION: wgpu::TextureViewDimension = wgpu::TextureViewDimension::D2;
+    /// The array layer sampled by default when the view dimension is `D2Array`.
+    pub const DEFAULT_LAYER: u32 = 0;
+    /// The filter mode used by default when scaling the source texture.
+    pub const DEFAULT_FILTER_MODE: wgpu::FilterMode = wgpu::FilterMode::Linear;
+    /// The color conversion applied by default, leaving the sampled colors unchanged.
+    pub const DEFAULT_COLOR_CONVERSION: ColorConversion = ColorConversion::None;
+
+    /// Begin building a `Reshaper`.
+    pub fn new(
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+    ) -> Self {
+        Builder {
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            src_rect: full_src_rect(),
+            view_dimension: Self::DEFAULT_VIEW_DIMENSION,
+            layer: Self::DEFAULT_LAYER,
+            filter_mode: Self::DEFAULT_FILTER_MODE,
+            color_conversion: Self::DEFAULT_COLOR_CONVERSION,
+        }
+    }
+
+    /// Write only the given region of the source texture to the entire destination texture.
+    ///
+    /// See `Reshaper::with_src_rect` for details.
+    pub fn src_rect(mut self, src_rect: geom::Rect<f32>) -> Self {
+        self.src_rect = src_rect;
+        self
+    }
+
+    /// The dimension of the source texture view.
+    ///
+    /// Supported dimensions are `D2` and `D2Array`. When reshaping a `D2Array` view, only the
+    /// selected layer is written to the destination. See `layer` and `Reshaper::set_layer`.
+    pub fn view_dimension(mut self, view_dimension: wgpu::TextureViewDimension) -> Self {
+        self.view_dimension = view_dimension;
+        self
+    }
+
+    /// The array layer of a `D2Array` source texture view to write to the destination.
+    pub fn layer(mut self, layer: u32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// The filter used for both magnification and minification when the source is scaled to the
+    /// destination.
+    ///
+    /// `Linear` interpolates between neighbouring texels, producing smooth results that suit
+    /// photographic or rendered content and softening moderate downscaling. `Nearest` selects a
+    /// single texel, preserving hard edges, which suits pixel-art style upscaling by whole factors
+    /// and exact copies between textures of the same size.
+    ///
+    /// Multisampled sources are resolved per texel, so the filter mode has no effect for them.
+    pub fn filter_mode(mut self, filter_mode: wgpu::FilterMode) -> Self {
+        self.filter_mode = filter_mode;
+        self
+    }
+
+    /// An explicit color space conversion to apply to the source colors.
+    ///
+    /// Multisampled sources are resolved in linear space. See `ColorConversion` for details.
+    pub fn color_conversion(mut self, color_conversion: ColorConversion) -> Self {
+        self.color_conversion = color_conversion;
+        self
+    }
+
+    /// Build the `Reshaper` for the given source texture view.
+    ///
+    /// **Panic!**s if the view dimension is unsupported, or if a `D2Array` source is multisampled
+    /// or has a color conversion.
+    pub fn build(self, device: &wgpu::Device, src_texture: &wgpu::TextureView) -> Reshaper {
+        let Builder {
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            src_rect,
+            view_dimension,
+            layer,
+            filter_mode,
+            color_conversion,
+        } = self;
+
         // Load shader modules.
         let vs = include_bytes!("shaders/vert.spv");
         let vs_spirv = wgpu::read_spirv(std::io::Cursor::new(&vs[..]))
             .expect("failed to read hard-coded SPIRV");
         let vs_mod = device.create_shader_module(&vs_spirv);
-        let fs = match src_sample_count {
-            1 => &include_bytes!("shaders/frag.spv")[..],
-            2 => &include_bytes!("shaders/frag_msaa2.spv")[..],
-            4 => &include_bytes!("shaders/frag_msaa4.spv")[..],
-            8 => &include_bytes!("shaders/frag_msaa8.spv")[..],
-            16 => &include_bytes!("shaders/frag_msaa16.spv")[..],
-            _ => &include_bytes!("shaders/frag_msaa.spv")[..],
+        let fs = match view_dimension {
+            wgpu::TextureViewDimension::D2 if color_conversion != ColorConversion::None => {
+                match src_sample_count {
+                    1 => &include_bytes!("shaders/frag_convert.spv")[..],
+                    _ => &include_bytes!("shaders/frag_msaa_convert.spv")[..],
+                }
+            }
+            wgpu::TextureViewDimension::D2 => match src_sample_count {
+                1 => &include_bytes!("shaders/frag.spv")[..],
+                2 => &include_bytes!("shaders/frag_msaa2.spv")[..],
+                4 => &include_bytes!("shaders/frag_msaa4.spv")[..],
+                8 => &include_bytes!("shaders/frag_msaa8.spv")[..],
+                16 => &include_bytes!("shaders/frag_msaa16.spv")[..],
+                _ => &include_bytes!("shaders/frag_msaa.spv")[..],
+            },
+            wgpu::TextureViewDimension::D2Array => {
+                assert_eq!(
+                    src_sample_count, 1,
+                    "multisampled `D2Array` source textures are not supported",
+                );
+                assert_eq!(
+                    color_conversion,
+                    ColorConversion::None,
+                    "color conversion is not supported for `D2Array` source textures",
+                );
+                &include_bytes!("shaders/frag_array.spv")[..]
+            }
+            dimension => panic!("unsupported source texture view dimension {:?}", dimension),
         };
         let fs_spirv =
             wgpu::read_spirv(std::io::Cursor::new(fs)).expect("failed to read hard-coded SPIRV");
         let fs_mod = device.create_shader_module(&fs_spirv);
 
         // Create the sampler for sampling from the source texture.
-        let sampler = wgpu::SamplerBuilder::new().build(device);
+        let sampler = wgpu::SamplerBuilder::new()
+            .mag_filter(filter_mode)
+            .min_filter(filter_mode)
+            .build(device);
 
         // Create the render pipeline.
-        let bind_group_layout = bind_group_layout(device, src_sample_count);
+        let bind_group_layout =
+            bind_group_layout(device, src_sample_count, view_dimension, color_conversion);
         let pipeline_layout = pipeline_layout(device, &bind_group_layout);
         let render_pipeline = render_pipeline(
             device,
@@ -71,34 +463,54 @@ impl Reshaper {
             dst_format,
         );
 
-        // Create the uniform buffer to pass the sample count if we don't have an unrolled resolve
-        // fragment shader for it.
-        let uniform_buffer = match unrolled_sample_count(src_sample_count) {
-            true => None,
-            false => {
-                let uniforms = Uniforms {
-                    sample_count: src_sample_count,
-                };
-                let buffer = device
-                    .create_buffer_mapped(1, wgpu::BufferUsage::UNIFORM)
-                    .fill_from_slice(&[uniforms]);
-                Some(buffer)
-            }
+        // Create the uniform buffer to pass the color conversion, the array layer, or the sample
+        // count if we don't have an unrolled resolve fragment shader for it.
+        let uniform_buffer = if color_conversion != ColorConversion::None {
+            let uniforms = ConversionUniforms {
+                sample_count: src_sample_count,
+                conversion: color_conversion.to_u32(),
+            };
+            let buffer = device
+                .create_buffer_mapped(1, wgpu::BufferUsage::UNIFORM)
+                .fill_from_slice(&[uniforms]);
+            Some(buffer)
+        } else if view_dimension == wgpu::TextureViewDimension::D2Array {
+            let uniforms = LayerUniforms { layer };
+            let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
+            let buffer = device
+                .create_buffer_mapped(1, usage)
+                .fill_from_slice(&[uniforms]);
+            Some(buffer)
+        } else if !unrolled_sample_count(src_sample_count) {
+            let uniforms = Uniforms {
+                sample_count: src_sample_count,
+            };
+            let buffer = device
+                .create_buffer_mapped(1, wgpu::BufferUsage::UNIFORM)
+                .fill_from_slice(&[uniforms]);
+            Some(buffer)
+        } else {
+            None
         };
 
         // Create the bind group.
-        let bind_group = bind_group(
-            device,
-            &bind_group_layout,
-            src_texture,
-            &sampler,
-            uniform_buffer.as_ref(),
-        );
+        let uniforms_size = uniforms_size(src_sample_count, view_dimension, color_conversion);
+        let uniforms = uniform_buffer
+            .as_ref()
+            .and_then(|b| uniforms_size.map(|size| (b, size)));
+        let bind_group = bind_group(device, &bind_group_layout, src_texture, &sampler, uniforms);
 
         // Create the vertex buffer.
+        let vertices = vertices(src_rect);
         let vertex_buffer = device
-            .create_buffer_mapped(VERTICES.len(), wgpu::BufferUsage::VERTEX)
-            .fill_from_slice(&VERTICES[..]);
+            .create_buffer_mapped(vertices.len(), wgpu::BufferUsage::VERTEX)
+            .fill_from_slice(&vertices[..]);
+
+        // Create the vertex buffer written by `encode_render_pass_with_transform`.
+        let usage = wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST;
+        let transformed_vertex_buffer = device
+            .create_buffer_mapped(vertices.len(), usage)
+            .fill_from_slice(&vertices[..]);
 
         Reshaper {
             _vs_mod: vs_mod,
@@ -109,52 +521,117 @@ impl Reshaper {
             sampler,
             uniform_buffer,
             vertex_buffer,
+            transformed_vertex_buffer,
+            view_dimension,
+            src_rect,
         }
     }
+}
 
-    /// Given an encoder, submits a render pass command for writing the source texture to the
-    /// destination texture.
-    pub fn encode_render_pass(
-        &self,
-        dst_texture: &wgpu::TextureView,
-        encoder: &mut wgpu::CommandEncoder,
-    ) {
-        let mut render_pass = wgpu::RenderPassBuilder::new()
-            .color_attachment(dst_texture, |color| color)
-            .begin(encoder);
-        render_pass.set_pipeline(&self.render_pipeline);
-        render_pass.set_vertex_buffers(0, &[(&self.vertex_buffer, 0)]);
-        render_pass.set_bind_group(0, &self.bind_group, &[]);
-        let vertex_range = 0..VERTICES.len() as u32;
-        let instance_range = 0..1;
-        render_pass.draw(vertex_range, instance_range);
+impl SrgbConversion {
+    /// Determine the conversion applied when reshaping from `src_format` to `dst_format`.
+    ///
+    /// This only inspects the two formats. It does not configure any `Reshaper`.
+    pub fn between(src_format: wgpu::TextureFormat, dst_format: wgpu::TextureFormat) -> Self {
+        let src_srgb = wgpu::texture_format_is_srgb(src_format);
+        let dst_srgb = wgpu::texture_format_is_srgb(dst_format);
+        match (src_srgb, dst_srgb) {
+            (true, false) => SrgbConversion::Decode,
+            (false, true) => SrgbConversion::Encode,
+            _ => SrgbConversion::None,
+        }
+    }
+}
+
+impl ColorConversion {
+    /// Apply the conversion to a single color channel value.
+    pub fn convert(&self, x: f32) -> f32 {
+        let x = x.max(0.0).min(1.0);
+        match *self {
+            ColorConversion::None => x,
+            ColorConversion::LinearToSrgb => {
+                if x <= 0.0031308 {
+                    x * 12.92
+                } else {
+                    1.055 * x.powf(1.0 / 2.4) - 0.055
+                }
+            }
+            ColorConversion::SrgbToLinear => {
+                if x <= 0.04045 {
+                    x / 12.92
+                } else {
+                    ((x + 0.055) / 1.055).powf(2.4)
+                }
+            }
+        }
+    }
+
+    // The value used to select the conversion within the fragment shader.
+    fn to_u32(&self) -> u32 {
+        match *self {
+            ColorConversion::None => 0,
+            ColorConversion::LinearToSrgb => 1,
+            ColorConversion::SrgbToLinear => 2,
+        }
+    }
+}
+
+impl Default for ColorConversion {
+    fn default() -> Self {
+        ColorConversion::None
+    }
+}
+
+impl Eye {
+    /// The region of a side-by-side stereo texture occupied by this eye's view, described in
+    /// normalised texture coordinates.
+    pub fn src_rect(&self) -> geom::Rect<f32> {
+        let (left, right) = match *self {
+            Eye::Left => (0.0, 0.5),
+            Eye::Right => (0.5, 1.0),
+        };
+        geom::Rect::from_corners([left, 0.0].into(), [right, 1.0].into())
     }
 }
 
-impl wgpu::VertexDescriptor for Vertex {
-    const STRIDE: wgpu::BufferAddress = std::mem::size_of::<Vertex>() as _;
-    const ATTRIBUTES: &'static [wgpu::VertexAttributeDescriptor] =
-        &[wgpu::VertexAttributeDescriptor {
-            format: wgpu::VertexFormat::Float2,
-            offset: 0,
-            shader_location: 0,
-        }];
-}
-
-const VERTICES: [Vertex; 4] = [
-    Vertex {
-        position: [-1.0, -1.0],
-    },
-    Vertex {
-        position: [-1.0, 1.0],
-    },
-    Vertex {
-        position: [1.0, -1.0],
-    },
-    Vertex {
-        position: [1.0, 1.0],
-    },
-];
+const NUM_VERTICES: usize = 4;
+
+// The full source texture in normalised texture coordinates.
+fn full_src_rect() -> geom::Rect<f32> {
+    geom::Rect::from_corners([0.0, 0.0].into(), [1.0, 1.0].into())
+}
+
+// The triangle strip covering the destination, sampling from the given region of the source.
+fn vertices(src_rect: geom::Rect<f32>) -> [Vertex; NUM_VERTICES] {
+    let vertex = |x: f32, y: f32| {
+        let tex_x = src_rect.x.lerp((x + 1.0) * 0.5);
+        let tex_y = src_rect.y.lerp((y + 1.0) * 0.5);
+        Vertex {
+            position: [x, y],
+            tex_coords: [tex_x, tex_y],
+        }
+    };
+    [
+        vertex(-1.0, -1.0),
+        vertex(-1.0, 1.0),
+        vertex(1.0, -1.0),
+        vertex(1.0, 1.0),
+    ]
+}
+
+// The triangle strip covering the destination with each position mapped by the given transform.
+fn transformed_vertices(
+    src_rect: geom::Rect<f32>,
+    transform: Matrix3<f32>,
+) -> [Vertex; NUM_VERTICES] {
+    let mut vertices = vertices(src_rect);
+    for v in vertices.iter_mut() {
+        let [x, y] = v.position;
+        let p = transform * cgmath::Vector3::new(x, y, 1.0);
+        v.position = [p.x / p.z, p.y / p.z];
+    }
+    vertices
+}
 
 // We provide pre-prepared fragment shaders with unrolled resolves for common sample counts.
 fn unrolled_sample_count(sample_count: u32) -> bool {
@@ -164,31 +641,57 @@ fn unrolled_sample_count(sample_count: u32) -> bool {
     }
 }
 
-fn bind_group_layout(device: &wgpu::Device, src_sample_count: u32) -> wgpu::BindGroupLayout {
+// The size of the uniforms required by the fragment shader, if any.
+//
+// Color conversions require the conversion and sample count, `D2Array` sources require the layer,
+// while multisampled sources without an unrolled resolve fragment shader require the sample count.
+fn uniforms_size(
+    src_sample_count: u32,
+    view_dimension: wgpu::TextureViewDimension,
+    color_conversion: ColorConversion,
+) -> Option<wgpu::BufferAddress> {
+    if color_conversion != ColorConversion::None {
+        Some(std::mem::size_of::<ConversionUniforms>() as _)
+    } else if view_dimension == wgpu::TextureViewDimension::D2Array {
+        Some(std::mem::size_of::<LayerUniforms>() as _)
+    } else if !unrolled_sample_count(src_sample_count) {
+        Some(std::mem::size_of::<Uniforms>() as _)
+    } else {
+        None
+    }
+}
+
+fn bind_group_layout(
+    device: &wgpu::Device,
+    src_sample_count: u32,
+    view_dimension: wgpu::TextureViewDimension,
+    color_conversion: ColorConversion,
+) -> wgpu::BindGroupLayout {
     let mut builder = wgpu::BindGroupLayoutBuilder::new()
         .sampled_texture(
             wgpu::ShaderStage::FRAGMENT,
             src_sample_count > 1,
-            wgpu::TextureViewDimension::D2,
+            view_dimension,
         )
         .sampler(wgpu::ShaderStage::FRAGMENT);
-    if !unrolled_sample_count(src_sample_count) {
+    if uniforms_size(src_sample_count, view_dimension, color_conversion).is_some() {
         builder = builder.uniform_buffer(wgpu::ShaderStage::FRAGMENT, false);
     }
     builder.build(device)
 }
+
 fn bind_group(
     device: &wgpu::Device,
     layout: &wgpu::BindGroupLayout,
     texture: &wgpu::TextureView,
     sampler: &wgpu::Sampler,
-    uniform_buffer: Option<&wgpu::Buffer>,
+    uniforms: Option<(&wgpu::Buffer, wgpu::BufferAddress)>,
 ) -> wgpu::BindGroup {
     let mut builder = wgpu::BindGroupBuilder::new()
         .texture_view(texture)
         .sampler(sampler);
-    if let Some(buffer) = uniform_buffer {
-        builder = builder.buffer::<Uniforms>(buffer, 0..1);
+    if let Some((buffer, size)) = uniforms {
+        builder = builder.buffer_bytes(buffer, 0..size);
     }
     builder.build(device, layout)
 }
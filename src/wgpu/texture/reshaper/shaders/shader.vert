@@ -7,9 +7,10 @@
 #version 450
 
 layout(location = 0) in vec2 position;
+layout(location = 1) in vec2 src_tex_coords;
 layout(location = 0) out vec2 tex_coords;
 
 void main() {
     gl_Position = vec4(position, 0.0, 1.0);
-    tex_coords = (position + vec2(1.0)) * 0.5;
+    tex_coords = src_tex_coords;
 }
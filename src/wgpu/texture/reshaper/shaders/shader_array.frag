@@ -0,0 +1,21 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag_array.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag_array.spv shader_array.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2DArray tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+layout(set = 0, binding = 2) uniform Data {
+    uint layer;
+} uniforms;
+
+void main() {
+    vec3 coords = vec3(tex_coords, float(uniforms.layer));
+    f_color = texture(sampler2DArray(tex, tex_sampler), coords);
+}
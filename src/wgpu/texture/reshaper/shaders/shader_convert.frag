@@ -0,0 +1,44 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag_convert.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag_convert.spv shader_convert.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+layout(set = 0, binding = 2) uniform Data {
+    uint sample_count;
+    uint conversion;
+} uniforms;
+
+// Matches `ColorConversion::to_u32`.
+const uint CONVERSION_LINEAR_TO_SRGB = 1;
+const uint CONVERSION_SRGB_TO_LINEAR = 2;
+
+vec3 linear_to_srgb(vec3 c) {
+    vec3 lo = c * 12.92;
+    vec3 hi = 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055;
+    return mix(hi, lo, lessThanEqual(c, vec3(0.0031308)));
+}
+
+vec3 srgb_to_linear(vec3 c) {
+    vec3 lo = c / 12.92;
+    vec3 hi = pow((c + 0.055) / 1.055, vec3(2.4));
+    return mix(hi, lo, lessThanEqual(c, vec3(0.04045)));
+}
+
+void main() {
+    vec4 color = texture(sampler2D(tex, tex_sampler), tex_coords);
+    vec3 rgb = clamp(color.rgb, 0.0, 1.0);
+    if (uniforms.conversion == CONVERSION_LINEAR_TO_SRGB) {
+        rgb = linear_to_srgb(rgb);
+    } else if (uniforms.conversion == CONVERSION_SRGB_TO_LINEAR) {
+        rgb = srgb_to_linear(rgb);
+    }
+    f_color = vec4(rgb, color.a);
+}
@@ -0,0 +1,60 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag_msaa_convert.spv`. You can do so using `glslangValidator` with
+// the following command:
+// `glslangValidator -V -o frag_msaa_convert.spv shader_msaa_convert.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2DMS tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+layout(set = 0, binding = 2) uniform Data {
+    uint sample_count;
+    uint conversion;
+} uniforms;
+
+// Matches `ColorConversion::to_u32`.
+const uint CONVERSION_LINEAR_TO_SRGB = 1;
+const uint CONVERSION_SRGB_TO_LINEAR = 2;
+
+vec3 linear_to_srgb(vec3 c) {
+    vec3 lo = c * 12.92;
+    vec3 hi = 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055;
+    return mix(hi, lo, lessThanEqual(c, vec3(0.0031308)));
+}
+
+vec3 srgb_to_linear(vec3 c) {
+    vec3 lo = c / 12.92;
+    vec3 hi = pow((c + 0.055) / 1.055, vec3(2.4));
+    return mix(hi, lo, lessThanEqual(c, vec3(0.04045)));
+}
+
+void main() {
+    // Get the integer tex coordinates.
+    ivec2 tex_size = textureSize(sampler2DMS(tex, tex_sampler));
+    int tex_x = int(tex_size.x * tex_coords.x);
+    int tex_y = int(tex_size.y * tex_coords.y);
+    ivec2 itex_coords = ivec2(tex_x, tex_y);
+
+    // Perform the resolve in linear space.
+    vec4 color = vec4(0);
+    for (int i = 0; i < uniforms.sample_count; i++) {
+        vec4 s = texelFetch(sampler2DMS(tex, tex_sampler), itex_coords, i);
+        s.rgb = clamp(s.rgb, 0.0, 1.0);
+        if (uniforms.conversion == CONVERSION_SRGB_TO_LINEAR) {
+            s.rgb = srgb_to_linear(s.rgb);
+        }
+        color += s;
+    }
+    color /= float(uniforms.sample_count);
+    if (uniforms.conversion == CONVERSION_LINEAR_TO_SRGB) {
+        color.rgb = linear_to_srgb(color.rgb);
+    }
+
+    // Assign the resolved color to the output.
+    f_color = color;
+}
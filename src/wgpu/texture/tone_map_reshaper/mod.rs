@@ -0,0 +1,208 @@
+use super::quad::FullscreenQuad;
+use crate::wgpu;
+
+/// Writes a high dynamic range texture to a low dynamic range texture by applying a tone mapping
+/// operator.
+///
+/// This is typically the final step of an HDR pipeline, where a scene has been rendered to a
+/// floating point texture (e.g. `Rgba16Float`) and must be presented on an LDR swap chain.
+///
+/// The `src_texture` must have the `TextureUsage::SAMPLED` enabled and must not be multisampled.
+/// Multisampled textures should first be resolved.
+///
+/// The `dst_texture` must have the `TextureUsage::OUTPUT_ATTACHMENT` enabled.
+#[derive(Debug)]
+pub struct ToneMapReshaper {
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    quad: FullscreenQuad,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    operator: ToneMapOperator,
+    exposure: f32,
+}
+
+/// The operator used to map HDR colors into the LDR range.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ToneMapOperator {
+    /// The simple Reinhard operator `x / (1 + x)`.
+    Reinhard,
+    /// Krzysztof Narkowicz's fitted approximation of the ACES filmic tone curve.
+    AcesFilmic,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Uniforms {
+    exposure: f32,
+    operator: u32,
+}
+
+impl ToneMapReshaper {
+    /// The exposure used by default, leaving the HDR color unscaled.
+    pub const DEFAULT_EXPOSURE: f32 = 1.0;
+
+    /// Construct a new `ToneMapReshaper`.
+    pub fn new(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+        operator: ToneMapOperator,
+        exposure: f32,
+    ) -> Self {
+        // Create the sampler for sampling from the source texture.
+        let sampler = wgpu::SamplerBuilder::new().build(device);
+
+        // Create the render pipeline.
+        let bind_group_layout = bind_group_layout(device);
+        let fs = include_bytes!("shaders/frag.spv");
+        let quad = FullscreenQuad::new(
+            device,
+            &bind_group_layout,
+            &fs[..],
+            dst_sample_count,
+            dst_format,
+        );
+
+        // Create the uniform buffer for the operator and exposure.
+        let uniforms = Uniforms::new(operator, exposure);
+        let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
+        let uniform_buffer = device
+            .create_buffer_mapped(1, usage)
+            .fill_from_slice(&[uniforms]);
+
+        // Create the bind group.
+        let bind_group = bind_group(
+            device,
+            &bind_group_layout,
+            src_texture,
+            &sampler,
+            &uniform_buffer,
+        );
+
+        ToneMapReshaper {
+            bind_group_layout,
+            bind_group,
+            quad,
+            sampler,
+            uniform_buffer,
+            operator,
+            exposure,
+        }
+    }
+
+    /// The tone mapping operator currently in use.
+    pub fn operator(&self) -> ToneMapOperator {
+        self.operator
+    }
+
+    /// The exposure by which HDR colors are scaled before the operator is applied.
+    pub fn exposure(&self) -> f32 {
+        self.exposure
+    }
+
+    /// Update the operator and exposure used by subsequent render passes.
+    ///
+    /// The new values are uploaded to the GPU via a copy command encoded with the given encoder.
+    pub fn set_uniforms(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        operator: ToneMapOperator,
+        exposure: f32,
+    ) {
+        self.operator = operator;
+        self.exposure = exposure;
+        let uniforms = Uniforms::new(operator, exposure);
+        let size = std::mem::size_of::<Uniforms>() as wgpu::BufferAddress;
+        let new_uniform_buffer = device
+            .create_buffer_mapped(1, wgpu::BufferUsage::COPY_SRC)
+            .fill_from_slice(&[uniforms]);
+        encoder.copy_buffer_to_buffer(&new_uniform_buffer, 0, &self.uniform_buffer, 0, size);
+    }
+
+    /// Given an encoder, submits a render pass command for writing the tone mapped source texture
+    /// to the destination texture.
+    pub fn encode_render_pass(
+        &self,
+        dst_texture: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        self.quad
+            .encode_render_pass(dst_texture, &self.bind_group, encoder);
+    }
+}
+
+impl ToneMapOperator {
+    /// Apply the operator to a single HDR color channel value that has already been scaled by the
+    /// exposure.
+    pub fn map(&self, x: f32) -> f32 {
+        let x = x.max(0.0);
+        match *self {
+            ToneMapOperator::Reinhard => x / (1.0 + x),
+            ToneMapOperator::AcesFilmic => {
+                let num = x * (2.51 * x + 0.03);
+                let den = x * (2.43 * x + 0.59) + 0.14;
+                (num / den).max(0.0).min(1.0)
+            }
+        }
+    }
+
+    /// Apply the operator to each channel of the given HDR color after scaling it by `exposure`.
+    pub fn map_rgb(&self, rgb: [f32; 3], exposure: f32) -> [f32; 3] {
+        let [r, g, b] = rgb;
+        [
+            self.map(r * exposure),
+            self.map(g * exposure),
+            self.map(b * exposure),
+        ]
+    }
+
+    // The value used to select the operator within the fragment shader.
+    fn to_u32(&self) -> u32 {
+        match *self {
+            ToneMapOperator::Reinhard => 0,
+            ToneMapOperator::AcesFilmic => 1,
+        }
+    }
+}
+
+impl Uniforms {
+    fn new(operator: ToneMapOperator, exposure: f32) -> Self {
+        let operator = operator.to_u32();
+        Uniforms { exposure, operator }
+    }
+}
+
+impl Default for ToneMapOperator {
+    fn default() -> Self {
+        ToneMapOperator::AcesFilmic
+    }
+}
+
+fn bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    wgpu::BindGroupLayoutBuilder::new()
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+        )
+        .sampler(wgpu::ShaderStage::FRAGMENT)
+        .uniform_buffer(wgpu::ShaderStage::FRAGMENT, false)
+        .build(device)
+}
+
+fn bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    texture: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+    uniform_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    wgpu::BindGroupBuilder::new()
+        .texture_view(texture)
+        .sampler(sampler)
+        .buffer::<Uniforms>(uniform_buffer, 0..1)
+        .build(device, layout)
+}
@@ -0,0 +1,41 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag.spv shader.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+layout(set = 0, binding = 2) uniform Data {
+    float exposure;
+    uint operator;
+} uniforms;
+
+// Matches `ToneMapOperator::Reinhard`.
+vec3 reinhard(vec3 x) {
+    return x / (vec3(1.0) + x);
+}
+
+// Matches `ToneMapOperator::AcesFilmic`.
+vec3 aces_filmic(vec3 x) {
+    vec3 num = x * (2.51 * x + vec3(0.03));
+    vec3 den = x * (2.43 * x + vec3(0.59)) + vec3(0.14);
+    return clamp(num / den, 0.0, 1.0);
+}
+
+void main() {
+    vec4 hdr = texture(sampler2D(tex, tex_sampler), tex_coords);
+    vec3 x = max(hdr.rgb * uniforms.exposure, vec3(0.0));
+    vec3 ldr;
+    if (uniforms.operator == 0) {
+        ldr = reinhard(x);
+    } else {
+        ldr = aces_filmic(x);
+    }
+    f_color = vec4(ldr, clamp(hdr.a, 0.0, 1.0));
+}
@@ -0,0 +1,29 @@
+use nannou::camera;
+use nannou::geom::Cuboid;
+use nannou::math::cgmath::Vector4;
+
+#[test]
+fn frame_bounds_test() {
+    let bounds = Cuboid::from_xyz_whd([0.0, 0.0, 0.0].into(), [1.0, 1.0, 1.0].into());
+    let view_proj = camera::frame_bounds(bounds, 16.0 / 9.0, std::f32::consts::PI / 3.0);
+    for corner in bounds.corners().iter() {
+        let clip = view_proj * Vector4::new(corner.x, corner.y, corner.z, 1.0);
+        let ndc = clip.truncate() / clip.w;
+        assert!(
+            ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0,
+            "{:?} out of view",
+            ndc
+        );
+        assert!(ndc.z >= 0.0 && ndc.z <= 1.0, "{:?} out of depth range", ndc);
+    }
+}
+
+#[test]
+fn frame_degenerate_bounds_test() {
+    let bounds = Cuboid::from_xyz_whd([1.0, 2.0, 3.0].into(), [0.0, 0.0, 0.0].into());
+    let view_proj = camera::frame_bounds(bounds, 1.0, std::f32::consts::PI / 3.0);
+    let clip = view_proj * Vector4::new(1.0, 2.0, 3.0, 1.0);
+    let ndc = clip.truncate() / clip.w;
+    assert!(ndc.x.abs() < 1e-4 && ndc.y.abs() < 1e-4);
+    assert!(ndc.z >= 0.0 && ndc.z <= 1.0);
+}
@@ -0,0 +1,40 @@
+// Helpers shared between the integration tests that require a GPU.
+//
+// Tests using these helpers return early when no adapter is available, e.g. on headless CI.
+#![allow(dead_code)]
+
+use nannou::wgpu;
+
+/// Request a device and queue from the default adapter, or `None` if there is no adapter.
+pub fn device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let adapter = wgpu::Adapter::request(&wgpu::DEFAULT_ADAPTER_REQUEST_OPTIONS)?;
+    Some(adapter.request_device(&wgpu::default_device_descriptor()))
+}
+
+/// Load the given SPIR-V bytes as a shader module.
+pub fn shader_module(device: &wgpu::Device, spirv: &[u8]) -> wgpu::ShaderModule {
+    let spirv = wgpu::read_spirv(std::io::Cursor::new(spirv)).expect("failed to read SPIR-V");
+    device.create_shader_module(&spirv)
+}
+
+/// The vertex shader used by the draw renderer, compatible with `draw::backend::wgpu::Vertex`.
+pub const DRAW_VERT_SPV: &[u8] = include_bytes!("../../src/draw/backend/wgpu/shaders/vert.spv");
+
+/// Submit the given encoder and read back the bytes of `texture` once it has finished.
+pub fn read_texture(
+    device: &wgpu::Device,
+    queue: &mut wgpu::Queue,
+    mut encoder: wgpu::CommandEncoder,
+    texture: &wgpu::Texture,
+) -> Vec<u8> {
+    let buffer = texture.to_buffer_bytes(device, &mut encoder);
+    queue.submit(&[encoder.finish()]);
+    let bytes = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+    let bytes2 = bytes.clone();
+    buffer.read(move |result| {
+        let mapping = result.expect("failed to map texture buffer");
+        bytes2.borrow_mut().extend_from_slice(mapping.data);
+    });
+    device.poll(true);
+    bytes.replace(vec![])
+}
@@ -0,0 +1,730 @@
+use nannou::color::lin_srgba;
+use nannou::draw::backend::wgpu::Vertex;
+use nannou::draw::mesh;
+use nannou::geom::pt2;
+use nannou::wgpu;
+use nannou::Draw;
+
+mod common;
+
+// Count the vertices produced by a large circle drawn with the given tolerance.
+fn circle_vertex_count(tolerance: f32) -> usize {
+    let draw: Draw = Draw::new();
+    draw.ellipse().radius(1000.0).tolerance(tolerance);
+    draw.raw_vertices().count()
+}
+
+#[test]
+fn tolerance_test() {
+    assert!(circle_vertex_count(0.01) > circle_vertex_count(1.0));
+}
+
+#[test]
+fn draw_tolerance_test() {
+    let draw: Draw = Draw::new();
+    draw.tolerance(0.01);
+    draw.ellipse().radius(1000.0);
+    let fine = draw.raw_vertices().count();
+    draw.reset();
+    draw.tolerance(1.0);
+    draw.ellipse().radius(1000.0);
+    let coarse = draw.raw_vertices().count();
+    assert!(fine > coarse);
+}
+
+#[test]
+fn glyph_colors_test() {
+    let red = lin_srgba(1.0, 0.0, 0.0, 1.0);
+    let blue = lin_srgba(0.0, 0.0, 1.0, 1.0);
+    let draw: Draw = Draw::new();
+    draw.text("ab").glyph_colors(vec![red, blue]);
+    let colors: Vec<_> = draw.raw_vertices().map(|v| v.vertex.color).collect();
+    let first_blue = colors
+        .iter()
+        .position(|c| *c == blue)
+        .expect("no vertices for the second glyph");
+    assert!(first_blue > 0);
+    assert!(colors[..first_blue].iter().all(|c| *c == red));
+    assert!(colors[first_blue..].iter().all(|c| *c == blue));
+}
+
+#[test]
+fn stamp_along_test() {
+    let single = {
+        let draw: Draw = Draw::new();
+        draw.rect().w_h(2.0, 2.0);
+        draw.raw_vertices().count()
+    };
+    let draw: Draw = Draw::new();
+    let path = [pt2(0.0, 0.0), pt2(100.0, 0.0)];
+    draw.rect().w_h(2.0, 2.0).stamp_along(&path, 10.0);
+    assert_eq!(draw.raw_vertices().count(), single * 11);
+}
+
+#[test]
+fn stamp_along_oriented_test() {
+    // A flat rect stamped along a diagonal path should lie along the diagonal, i.e. each corner
+    // should be within half the rect's height of the line `y = x`.
+    let draw: Draw = Draw::new();
+    let path = [pt2(0.0, 0.0), pt2(100.0, 100.0)];
+    draw.rect().w_h(20.0, 2.0).stamp_along_oriented(&path, 50.0);
+    assert!(draw.raw_vertices().count() > 0);
+    for v in draw.raw_vertices() {
+        let p = v.point();
+        assert!((p.x - p.y).abs() <= 2.0f32.sqrt() + 1e-3);
+    }
+}
+
+#[test]
+fn rect_depth_test() {
+    // Specifying a depth should not panic. The rect remains flat at its z position.
+    let draw: Draw = Draw::new();
+    draw.rect().w_h_d(20.0, 10.0, 30.0).z(5.0);
+    assert!(draw.raw_vertices().count() > 0);
+    for v in draw.raw_vertices() {
+        let p = v.point();
+        assert_eq!(p.x.abs(), 10.0);
+        assert_eq!(p.y.abs(), 5.0);
+        assert_eq!(p.z, 5.0);
+    }
+}
+
+#[test]
+fn jitter_test() {
+    let rect_points = |jitter: Option<u64>| {
+        let draw: Draw = Draw::new();
+        let rect = draw.rect().w_h(100.0, 100.0);
+        if let Some(seed) = jitter {
+            rect.jitter(5.0, seed);
+        }
+        draw.raw_vertices()
+            .map(|v| v.vertex.vertex)
+            .collect::<Vec<_>>()
+    };
+    let plain = rect_points(None);
+    let a = rect_points(Some(1));
+    let b = rect_points(Some(1));
+    let c = rect_points(Some(2));
+    assert_eq!(a, b);
+    assert_ne!(a, plain);
+    assert_ne!(a, c);
+
+    // The offsets for a given seed are stable, so a sketch always renders the same way.
+    let expected = [
+        [-49.937240, -53.819038],
+        [49.556847, -52.616127],
+        [53.671051, 50.027905],
+        [-53.038361, 45.490925],
+    ];
+    for &[x, y] in expected.iter() {
+        assert!(
+            a.iter()
+                .any(|v| (v.x - x).abs() < 1e-4 && (v.y - y).abs() < 1e-4),
+            "no vertex at [{}, {}] in {:?}",
+            x,
+            y,
+            a,
+        );
+    }
+}
+
+#[test]
+fn backend_vertex_round_trip_test() {
+    let point = [120.0, -45.0, 10.0].into();
+    let color = lin_srgba(0.2, 0.4, 0.6, 0.8);
+    let tex_coords = [0.25, 0.75].into();
+    let v: mesh::Vertex = mesh::vertex::new(point, color, tex_coords);
+    let (w, h, dpi) = (640.0, 480.0, 2.0);
+    let backend_v = Vertex::from_mesh_vertex(v, w, h, dpi);
+    let round_trip = backend_v.to_mesh_vertex(w, h, dpi);
+    let eq = |a: f32, b: f32| (a - b).abs() < 1e-4;
+    let (p, rp) = (v.point(), round_trip.point());
+    assert!(eq(p.x, rp.x) && eq(p.y, rp.y) && eq(p.z, rp.z));
+    assert_eq!(v.color, round_trip.color);
+    assert_eq!(v.tex_coords, round_trip.tex_coords);
+}
+
+#[test]
+fn ring_test() {
+    let draw: Draw = Draw::new();
+    draw.ring().inner_radius(40.0).outer_radius(50.0);
+    let mut count = 0;
+    for v in draw.raw_vertices() {
+        let p = v.vertex.vertex;
+        let r = (p.x * p.x + p.y * p.y).sqrt();
+        assert!(
+            r >= 40.0 * 0.99 && r <= 50.0 + 1e-3,
+            "{} not within ring",
+            r
+        );
+        count += 1;
+    }
+    assert!(count > 0);
+
+    let draw: Draw = Draw::new();
+    draw.ring().inner_radius(50.0).outer_radius(40.0);
+    assert_eq!(draw.raw_vertices().count(), 0);
+}
+
+#[test]
+fn concave_polygon_test() {
+    // An arrow pointing along the positive x axis with a concave notch either side of the head.
+    let arrow = [
+        pt2(-50.0, -10.0),
+        pt2(10.0, -10.0),
+        pt2(10.0, -30.0),
+        pt2(50.0, 0.0),
+        pt2(10.0, 30.0),
+        pt2(10.0, 10.0),
+        pt2(-50.0, 10.0),
+    ];
+    let draw: Draw = Draw::new();
+    draw.polygon().points(arrow.iter().cloned());
+    let tris: Vec<_> = draw
+        .triangles()
+        .map(|tri| tri.map_vertices(|v| pt2(v.vertex.vertex.x, v.vertex.vertex.y)))
+        .collect();
+    assert!(!tris.is_empty());
+
+    // The triangulation covers exactly the area of the arrow.
+    let area: f32 = tris
+        .iter()
+        .map(|tri| {
+            let [a, b, c] = tri.0;
+            ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() * 0.5
+        })
+        .sum();
+    assert!((area - 2400.0).abs() < 1e-2, "unexpected area {}", area);
+
+    // The shaft is covered while the notches beside the head are not.
+    assert!(tris.iter().any(|tri| tri.contains(&pt2(0.0, 0.0))));
+    assert!(!tris.iter().any(|tri| tri.contains(&pt2(0.0, 20.0))));
+    assert!(!tris.iter().any(|tri| tri.contains(&pt2(0.0, -20.0))));
+
+    // Fewer than three points describe no area.
+    let draw: Draw = Draw::new();
+    draw.polygon()
+        .stroke(lin_srgba(1.0, 1.0, 1.0, 1.0))
+        .points(vec![pt2(0.0, 0.0), pt2(10.0, 10.0)]);
+    assert_eq!(draw.raw_vertices().count(), 0);
+}
+
+#[test]
+fn z_order_test() {
+    let red = lin_srgba(1.0, 0.0, 0.0, 1.0);
+    let blue = lin_srgba(0.0, 0.0, 1.0, 1.0);
+    let draw: Draw = Draw::new();
+    draw.rect().w_h(100.0, 100.0).color(red).z_order(1);
+    draw.rect().w_h(100.0, 100.0).color(blue).z_order(0);
+    let colors: Vec<_> = draw.raw_vertices().map(|v| v.vertex.color).collect();
+    let indices = draw.z_ordered_indices();
+    assert_eq!(indices.len(), draw.inner_mesh().indices().len());
+
+    // Red has the higher z-order and is drawn last, on top of blue.
+    let (first, last) = (indices[0], indices[indices.len() - 1]);
+    assert_eq!(colors[first], blue);
+    assert_eq!(colors[last], red);
+
+    // Equal z-orders retain the order in which primitives were drawn.
+    let draw: Draw = Draw::new();
+    draw.rect().w_h(100.0, 100.0).color(red).z_order(2);
+    draw.rect().w_h(100.0, 100.0).color(blue).z_order(2);
+    assert_eq!(
+        draw.z_ordered_indices(),
+        draw.inner_mesh().indices().to_vec()
+    );
+}
+
+#[test]
+fn axes_test() {
+    let red = lin_srgba(1.0, 0.0, 0.0, 1.0);
+    let green = lin_srgba(0.0, 1.0, 0.0, 1.0);
+    let blue = lin_srgba(0.0, 0.0, 1.0, 1.0);
+    let draw: Draw = Draw::new();
+    draw.axes(10.0);
+    let vertices: Vec<_> = draw.vertices().collect();
+    let axis = |color| {
+        let points: Vec<_> = vertices
+            .iter()
+            .filter(|v| v.vertex.color == color)
+            .map(|v| v.vertex.vertex)
+            .collect();
+        assert!(!points.is_empty());
+        points
+    };
+    let max = |a: f32, b: f32| a.max(b);
+    let eps = 1e-3;
+
+    // X axis.
+    let xs = axis(red);
+    assert!(xs.iter().all(|p| p.z.abs() < eps && p.x > -eps));
+    assert!((xs.iter().map(|p| p.x).fold(0.0, max) - 10.0).abs() < eps);
+
+    // Y axis.
+    let ys = axis(green);
+    assert!(ys.iter().all(|p| p.z.abs() < eps && p.y > -eps));
+    assert!((ys.iter().map(|p| p.y).fold(0.0, max) - 10.0).abs() < eps);
+
+    // Z axis.
+    let zs = axis(blue);
+    assert!(zs.iter().all(|p| p.x.abs() < eps && p.z > -eps));
+    assert!((zs.iter().map(|p| p.z).fold(0.0, max) - 10.0).abs() < eps);
+
+    assert_eq!(xs.len() + ys.len() + zs.len(), vertices.len());
+}
+
+#[test]
+fn logical_to_physical_pixel_test() {
+    use nannou::draw::backend::wgpu::logical_to_physical_pixel;
+    // A 50x25 logical frame with a scale factor of 2.
+    let size = [100, 50];
+    let scale_factor = 2.0;
+    let pixel = |x, y| logical_to_physical_pixel(pt2(x, y), size, scale_factor);
+
+    // The left and right halves of the frame.
+    assert_eq!(pixel(-10.0, 0.0), Some([30, 25]));
+    assert_eq!(pixel(10.0, 5.0), Some([70, 15]));
+
+    // The top edge is inclusive while the bottom and right edges are exclusive.
+    assert_eq!(pixel(0.0, 12.5), Some([50, 0]));
+    assert_eq!(pixel(0.0, -12.5), None);
+    assert_eq!(pixel(25.0, 0.0), None);
+    assert_eq!(pixel(-30.0, 0.0), None);
+}
+
+#[test]
+fn backend_vertex_depth_aspect_test() {
+    // The same point should map to the same depth regardless of the framebuffer's aspect ratio
+    // or scale factor.
+    let point = [120.0, -45.0, 250.0].into();
+    let color = lin_srgba(1.0, 1.0, 1.0, 1.0);
+    let v: mesh::Vertex = mesh::vertex::new(point, color, mesh::vertex::default_tex_coords());
+    let wide = Vertex::from_mesh_vertex(v, 1920.0, 1080.0, 1.0);
+    let tall = Vertex::from_mesh_vertex(v, 600.0, 1200.0, 2.0);
+    assert_eq!(wide.position[2], tall.position[2]);
+    assert_eq!(wide.position[2], 250.0 / Vertex::DEPTH_RANGE);
+}
+
+#[test]
+fn world_to_screen_test() {
+    use nannou::draw::backend::wgpu::{world_to_screen, Handedness};
+    use nannou::geom::pt3;
+    // A 50x25 logical frame with a scale factor of 2.
+    let size = [100, 50];
+    let scale_factor = 2.0;
+    let screen =
+        |x, y, z, handedness| world_to_screen(pt3(x, y, z), size, scale_factor, handedness);
+    let left = Handedness::LeftHanded;
+    let approx_eq = |p: nannou::geom::Point2, [x, y]: [f32; 2]| {
+        (p.x - x).abs() < 1e-3 && (p.y - y).abs() < 1e-3
+    };
+
+    // The origin maps to the centre of the frame and *y* increases downwards.
+    assert!(approx_eq(
+        screen(0.0, 0.0, 0.0, left).unwrap(),
+        [50.0, 25.0]
+    ));
+    assert!(approx_eq(
+        screen(-10.0, 5.0, 0.0, left).unwrap(),
+        [30.0, 15.0]
+    ));
+
+    // Points off the edge of the frame are still projected.
+    assert!(approx_eq(
+        screen(30.0, 0.0, 0.0, left).unwrap(),
+        [110.0, 25.0]
+    ));
+
+    // Points in front of the near plane are clipped, depending on the handedness.
+    assert!(screen(0.0, 0.0, -1.0, left).is_none());
+    assert!(screen(0.0, 0.0, 1.0, left).is_some());
+    assert!(screen(0.0, 0.0, 1.0, Handedness::RightHanded).is_none());
+}
+
+#[test]
+fn background_gradient_test() {
+    use nannou::draw::backend::wgpu::gradient_vertices;
+    use nannou::draw::background::Gradient;
+    let black = lin_srgba(0.0, 0.0, 0.0, 1.0);
+    let white = lin_srgba(1.0, 1.0, 1.0, 1.0);
+    let gradient = Gradient::Linear {
+        top: black,
+        bottom: white,
+    };
+    assert_eq!(gradient.color_at([0.0, 1.0]), black);
+    assert_eq!(gradient.color_at([0.0, -1.0]), white);
+    assert_eq!(gradient.color_at([0.5, 0.0]), lin_srgba(0.5, 0.5, 0.5, 1.0));
+
+    // The top of the frame is at -1.0 in wgpu's normalised device coordinates.
+    let vertices = gradient_vertices(&gradient);
+    assert_eq!(vertices.len(), 6);
+    for v in &vertices {
+        let expected = match v.position[1] {
+            y if y < 0.0 => [0.0, 0.0, 0.0, 1.0],
+            _ => [1.0, 1.0, 1.0, 1.0],
+        };
+        assert_eq!(v.color, expected);
+    }
+
+    // Transparent gradients keep their alpha.
+    let clear = lin_srgba(1.0, 0.0, 0.0, 0.0);
+    let gradient = Gradient::Radial {
+        inner: clear,
+        outer: black,
+    };
+    let vertices = gradient_vertices(&gradient);
+    assert_eq!(vertices.len(), Gradient::RADIAL_SEGMENTS * 3);
+    assert_eq!(vertices[0].position[..2], [0.0, 0.0]);
+    assert_eq!(vertices[0].color, [1.0, 0.0, 0.0, 0.0]);
+    assert_eq!(gradient.color_at([2.0, 0.0]), black);
+}
+
+#[test]
+fn export_obj_test() {
+    let (centre, size) = ([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]);
+    let cube: nannou::geom::Cuboid<f32> =
+        nannou::geom::Cuboid::from_xyz_whd(centre.into(), size.into());
+    let draw: Draw = Draw::new();
+    draw.mesh().tris(cube.triangles_iter());
+    let path = std::env::temp_dir().join("nannou_export_obj_test.obj");
+    draw.export_obj(&path).unwrap();
+    let obj = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let count = |prefix| obj.lines().filter(|l| l.starts_with(prefix)).count();
+    // Two triangles for each of the six faces.
+    assert_eq!(count("v "), 36);
+    assert_eq!(count("f "), 12);
+
+    // 2D geometry is exported with a z of zero.
+    let draw: Draw = Draw::new();
+    draw.rect().w_h(10.0, 10.0);
+    let mut obj = vec![];
+    draw.write_obj(&mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    let vertices: Vec<_> = obj.lines().filter(|l| l.starts_with("v ")).collect();
+    assert!(!vertices.is_empty());
+    assert!(vertices.iter().all(|l| l.ends_with(" 0")));
+}
+
+#[test]
+fn export_ply_test() {
+    let draw: Draw = Draw::new();
+    draw.tri().color(lin_srgba(1.0, 0.0, 0.0, 1.0));
+    let mut ply = vec![];
+    draw.write_ply(&mut ply).unwrap();
+    let ply = String::from_utf8(ply).unwrap();
+    let mut lines = ply.lines();
+    assert_eq!(lines.next(), Some("ply"));
+    assert!(ply.contains("element vertex 3\n"));
+    assert!(ply.contains("element face 1\n"));
+    let body: Vec<_> = lines.skip_while(|&l| l != "end_header").skip(1).collect();
+    assert_eq!(body.len(), 4);
+    assert!(body[..3].iter().all(|l| l.ends_with(" 255 0 0 255")));
+    let mut face: Vec<u32> = body[3].split(' ').map(|i| i.parse().unwrap()).collect();
+    face.sort();
+    assert_eq!(face, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn oit_composite_test() {
+    use nannou::draw::backend::wgpu::oit_composite;
+    let dst = lin_srgba(0.0, 0.0, 0.0, 1.0);
+    let red = (lin_srgba(1.0, 0.0, 0.0, 0.5), 0.2);
+    let green = (lin_srgba(0.0, 1.0, 0.0, 0.5), 0.5);
+    let blue = (lin_srgba(0.0, 0.0, 1.0, 0.5), 0.8);
+    let orders = [
+        [red, green, blue],
+        [red, blue, green],
+        [green, red, blue],
+        [green, blue, red],
+        [blue, red, green],
+        [blue, green, red],
+    ];
+    let first = oit_composite(dst, &orders[0]);
+    for order in orders.iter() {
+        let c = oit_composite(dst, order);
+        let (a, b) = (first, c);
+        let close = |x: f32, y: f32| (x - y).abs() < 1e-5;
+        assert!(close(a.red, b.red) && close(a.green, b.green) && close(a.blue, b.blue));
+        assert!(close(a.alpha, b.alpha));
+    }
+
+    // Nearer fragments carry more weight and the background is only partially revealed.
+    assert!(first.red > first.green && first.green > first.blue);
+    let revealed = 0.5 * 0.5 * 0.5;
+    assert!((first.red + first.green + first.blue - (1.0 - revealed)).abs() < 1e-5);
+
+    // No fragments leaves the destination untouched.
+    assert_eq!(oit_composite(dst, &[]), dst);
+}
+
+#[test]
+fn point_cloud_test() {
+    let small = lin_srgba(1.0, 0.0, 0.0, 1.0);
+    let large = lin_srgba(0.0, 0.0, 1.0, 1.0);
+    let draw: Draw = Draw::new();
+    let points = vec![
+        (nannou::geom::pt3(-10.0, 0.0, 0.0), 2.0, small),
+        (nannou::geom::pt3(10.0, 0.0, 0.0), 6.0, large),
+    ];
+    draw.point_cloud(points);
+    // Two triangles for each point.
+    assert_eq!(draw.inner_mesh().indices().len(), 12);
+    let area = |color| {
+        let triangles: Vec<_> = draw
+            .triangles()
+            .filter(|tri| tri[0].vertex.color == color)
+            .collect();
+        assert_eq!(triangles.len(), 2);
+        triangles
+            .iter()
+            .map(|tri| {
+                let (a, b, c) = (tri[0].point(), tri[1].point(), tri[2].point());
+                ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() * 0.5
+            })
+            .sum::<f32>()
+    };
+    assert!((area(small) - 4.0).abs() < 1e-4);
+    assert!((area(large) - 36.0).abs() < 1e-4);
+}
+
+#[test]
+fn record_test() {
+    let draw: Draw = Draw::new();
+    draw.background().color(lin_srgba(0.0, 0.0, 1.0, 1.0));
+    draw.rect().w_h(100.0, 50.0);
+    let commands = draw.record();
+    assert_eq!(commands.vertices().len(), draw.raw_vertices().count());
+    assert_eq!(commands.indices(), &draw.z_ordered_indices()[..]);
+    assert_eq!(
+        commands.background_color(),
+        Some(lin_srgba(0.0, 0.0, 1.0, 1.0))
+    );
+
+    // The recorded commands are unaffected by resetting the draw.
+    draw.reset();
+    assert!(commands.vertices().len() > 0);
+
+    // Replaying at different sizes maps the same geometry to different portions of the output.
+    let max_ndc_x = |size: f32| {
+        commands
+            .vertices()
+            .iter()
+            .map(|&v| Vertex::from_mesh_vertex(v, size, size, 1.0).position[0])
+            .fold(0.0f32, f32::max)
+    };
+    assert_eq!(max_ndc_x(100.0), 1.0);
+    assert_eq!(max_ndc_x(400.0), 0.25);
+}
+
+#[test]
+fn radial_repeat_test() {
+    let tri = |draw: &Draw| {
+        draw.tri()
+            .points(pt2(0.0, 0.0), pt2(20.0, 0.0), pt2(0.0, 10.0))
+            .x_y(50.0, 0.0)
+    };
+    let single: Draw = Draw::new();
+    tri(&single);
+    let original: Vec<_> = single.raw_vertices().map(|v| *v.point()).collect();
+
+    // A count of `0` or `1` yields only the original.
+    for &count in &[0, 1] {
+        let draw: Draw = Draw::new();
+        tri(&draw).radial_repeat(count);
+        assert_eq!(draw.raw_vertices().count(), original.len());
+    }
+
+    // Each copy is the original rotated a further 60 degrees about the origin.
+    let draw: Draw = Draw::new();
+    tri(&draw).radial_repeat(6);
+    let repeated: Vec<_> = draw.raw_vertices().map(|v| *v.point()).collect();
+    assert_eq!(repeated.len(), original.len() * 6);
+    for i in 0..6 {
+        let (sin, cos) = (i as f32 * std::f32::consts::PI / 3.0).sin_cos();
+        for p in &original {
+            let (x, y) = (p.x * cos - p.y * sin, p.x * sin + p.y * cos);
+            let found = repeated
+                .iter()
+                .any(|r| (r.x - x).abs() < 1e-3 && (r.y - y).abs() < 1e-3);
+            assert!(found, "no vertex found at [{}, {}]", x, y);
+        }
+    }
+}
+
+#[test]
+fn signed_distance_field_test() {
+    use nannou::draw::backend::wgpu::{signed_distance_field, Renderer};
+    // A circle with a radius of 20 pixels centred within a 64x64 texture.
+    let (w, h) = (64, 64);
+    let coverage: Vec<f32> = (0..w * h)
+        .map(|i| {
+            let (x, y) = ((i % w) as f32 + 0.5, (i / w) as f32 + 0.5);
+            let d = ((x - 32.0).powi(2) + (y - 32.0).powi(2)).sqrt();
+            if d <= 20.0 {
+                1.0
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let spread = Renderer::SDF_SPREAD;
+    let sdf = signed_distance_field(&coverage, [w as u32, h as u32], spread);
+    let row = &sdf[32 * w..33 * w];
+
+    // The edge of the circle lies between the 51st and 52nd texels of the centre row.
+    assert_eq!(row[51], -0.5);
+    assert_eq!(row[52], 0.5);
+
+    // Distances increase linearly away from the edge in both directions.
+    for x in 45..59 {
+        assert!((row[x + 1] - row[x] - 1.0).abs() < 1e-5);
+    }
+
+    // Distances are clamped to the spread.
+    assert_eq!(row[32], -(spread as f32));
+    assert_eq!(row[63], spread as f32);
+}
+
+#[test]
+fn sample_mask_coverage_test() {
+    use nannou::draw::backend::wgpu::Renderer;
+    // Half of the samples of a 4x target gives 50% coverage once resolved.
+    assert_eq!(Renderer::sample_mask_coverage(0b0101, 4), 0.5);
+    assert_eq!(Renderer::sample_mask_coverage(0b0001, 4), 0.25);
+    // Bits beyond the sample count are ignored.
+    assert_eq!(Renderer::sample_mask_coverage(0b1111_0000, 4), 0.0);
+    assert_eq!(
+        Renderer::sample_mask_coverage(Renderer::DEFAULT_SAMPLE_MASK, 4),
+        1.0
+    );
+    assert_eq!(Renderer::sample_mask_coverage(0, 1), 0.0);
+}
+
+#[test]
+fn blend_constant_cross_fade_test() {
+    use nannou::draw::backend::wgpu::{BlendMode, Renderer};
+    let (device, mut queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let size = [64, 64];
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let texture = wgpu::Texture::render_target(&device, size, format, 1);
+    let view = texture.create_default_view();
+    let desc = wgpu::CommandEncoderDescriptor::default();
+    let mut encoder = device.create_command_encoder(&desc);
+
+    // The first layer is cleared to red.
+    let draw = Draw::new();
+    draw.background().color(lin_srgba(1.0, 0.0, 0.0, 1.0));
+    let mut renderer = Renderer::new(&device, size, 1, format);
+    renderer.encode_render_pass(&device, &mut encoder, &draw, 1.0, size, &view, None);
+
+    // The second layer is an opaque blue rect cross-faded over the first by half.
+    let draw = Draw::new();
+    draw.rect()
+        .w_h(64.0, 64.0)
+        .color(lin_srgba(0.0, 0.0, 1.0, 1.0));
+    let mut renderer =
+        Renderer::new(&device, size, 1, format).with_blend_mode(&device, BlendMode::Constant);
+    renderer.set_blend_constant(Some(wgpu::Color {
+        r: 0.5,
+        g: 0.5,
+        b: 0.5,
+        a: 0.5,
+    }));
+    renderer.encode_render_pass_with_load_op(
+        &device,
+        &mut encoder,
+        &draw,
+        1.0,
+        size,
+        &view,
+        None,
+        wgpu::LoadOp::Load,
+    );
+
+    let bytes = common::read_texture(&device, &mut queue, encoder, &texture);
+    for px in bytes.chunks_exact(4) {
+        let expected = [128, 0, 128, 255];
+        for (&c, &e) in px.iter().zip(expected.iter()) {
+            assert!((c as i32 - e).abs() <= 2, "{:?} != {:?}", px, expected);
+        }
+    }
+}
+
+#[test]
+fn render_commands_test() {
+    use nannou::draw::backend::wgpu::Renderer;
+    let (device, mut queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let draw = Draw::new();
+    draw.background().color(lin_srgba(0.0, 0.0, 0.0, 1.0));
+    draw.rect()
+        .w_h(32.0, 32.0)
+        .color(lin_srgba(1.0, 1.0, 1.0, 1.0));
+    let commands = draw.record();
+
+    // Replay the same commands as a preview and as a high resolution export at twice the scale.
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let mut renderer = Renderer::new(&device, [64, 64], 1, format);
+    let mut covered = |size: [u32; 2], scale_factor: f32| {
+        let texture = wgpu::Texture::render_target(&device, size, format, 1);
+        let view = texture.create_default_view();
+        let desc = wgpu::CommandEncoderDescriptor::default();
+        let mut encoder = device.create_command_encoder(&desc);
+        renderer.render_commands(
+            &device,
+            &mut encoder,
+            &commands,
+            scale_factor,
+            size,
+            &view,
+            None,
+        );
+        let bytes = common::read_texture(&device, &mut queue, encoder, &texture);
+        bytes.chunks_exact(4).filter(|px| px[0] == 255).count()
+    };
+    assert_eq!(covered([64, 64], 1.0), 32 * 32);
+    assert_eq!(covered([128, 128], 2.0), 64 * 64);
+}
+
+#[test]
+fn render_to_atlas_regions_in_one_encoder_test() {
+    use nannou::draw::backend::wgpu::Renderer;
+    use nannou::geom::Rect;
+    let (device, mut queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let size = [64, 64];
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let atlas = wgpu::Texture::render_target(&device, size, format, 1);
+    let mut renderer = Renderer::new(&device, size, 1, format);
+    let desc = wgpu::CommandEncoderDescriptor::default();
+    let mut encoder = device.create_command_encoder(&desc);
+
+    // Both passes are encoded before submission, so the second upload must not overwrite the
+    // first before it has been copied.
+    let halves = [
+        (Rect::from_corners(pt2(0, 0), pt2(32, 64)), [1.0, 0.0]),
+        (Rect::from_corners(pt2(32, 0), pt2(64, 64)), [0.0, 1.0]),
+    ];
+    for &(region, [r, b]) in halves.iter() {
+        let draw = Draw::new();
+        draw.rect().w_h(32.0, 64.0).color(lin_srgba(r, 0.0, b, 1.0));
+        renderer.render_to_atlas_region(&device, &mut encoder, &draw, &atlas, region);
+    }
+
+    let bytes = common::read_texture(&device, &mut queue, encoder, &atlas);
+    for (i, px) in bytes.chunks_exact(4).enumerate() {
+        let expected = match i % 64 < 32 {
+            true => [255, 0, 0, 255],
+            false => [0, 0, 255, 255],
+        };
+        assert_eq!(px, expected, "pixel {}", i);
+    }
+}
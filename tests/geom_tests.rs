@@ -13,3 +13,44 @@ fn angle_test() {
     let vector = Vector2::new(70.7, -60.8);
     assert_eq!(vector.angle(), -0.7102547457375739);
 }
+
+#[test]
+fn polygon_morph_test() {
+    use nannou::geom::{polygon, pt2};
+    let square = [
+        pt2(-1.0, -1.0),
+        pt2(1.0, -1.0),
+        pt2(1.0, 1.0),
+        pt2(-1.0, 1.0),
+    ];
+    let wide = [
+        pt2(-3.0, -1.0),
+        pt2(3.0, -1.0),
+        pt2(3.0, 1.0),
+        pt2(-3.0, 1.0),
+    ];
+    let half = polygon::morph(&square, &wide, 0.5);
+    let expected = [
+        pt2(-2.0, -1.0),
+        pt2(2.0, -1.0),
+        pt2(2.0, 1.0),
+        pt2(-2.0, 1.0),
+    ];
+    assert_eq!(&half[..], &expected[..]);
+    assert_eq!(&polygon::morph(&square, &wide, 0.0)[..], &square[..]);
+    assert_eq!(&polygon::morph(&square, &wide, 1.0)[..], &wide[..]);
+
+    // The square is resampled to match the octagon's point count.
+    let octagon: Vec<_> = (0..8)
+        .map(|i| {
+            let a = i as f32 * std::f32::consts::PI / 4.0;
+            pt2(a.cos(), a.sin())
+        })
+        .collect();
+    assert_eq!(polygon::morph(&square, &octagon, 0.5).len(), 8);
+    let resampled = polygon::resample(&square, 8);
+    assert_eq!(resampled.len(), 8);
+    assert_eq!(resampled[0], square[0]);
+    assert_eq!(resampled[1], pt2(0.0, -1.0));
+    assert_eq!(resampled[2], square[1]);
+}
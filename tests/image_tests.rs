@@ -0,0 +1,60 @@
+use nannou::image::{self, AnimationDecoder, GifRecorder, GifRecorderError};
+
+fn solid(rgba: [u8; 4]) -> image::RgbaImage {
+    image::ImageBuffer::from_pixel(4, 4, image::Rgba(rgba))
+}
+
+#[test]
+fn gif_recorder_test() {
+    let colors = [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]];
+    let mut recorder = GifRecorder::new(100);
+    for &color in colors.iter() {
+        recorder.add_frame(solid(color)).unwrap();
+    }
+    assert_eq!(recorder.len(), 3);
+
+    let mut bytes = vec![];
+    recorder.finish(&mut bytes).unwrap();
+    let decoder = image::gif::GifDecoder::new(std::io::Cursor::new(bytes)).unwrap();
+    let frames = decoder.into_frames().collect_frames().unwrap();
+    assert_eq!(frames.len(), 3);
+    // Quantization may shift colors slightly, so compare within a small tolerance.
+    for (frame, &color) in frames.iter().zip(colors.iter()) {
+        let pixel = frame.buffer().get_pixel(0, 0).0;
+        for (&a, &b) in pixel.iter().zip(color.iter()) {
+            assert!(
+                (a as i32 - b as i32).abs() <= 8,
+                "{:?} != {:?}",
+                pixel,
+                color
+            );
+        }
+    }
+}
+
+#[test]
+fn gif_recorder_limits_test() {
+    let mut recorder = GifRecorder::new(100).max_frames(1).max_side(4);
+    match recorder.add_frame(image::ImageBuffer::new(8, 4)) {
+        Err(GifRecorderError::FrameTooLarge { size, max_side }) => {
+            assert_eq!(size, [8, 4]);
+            assert_eq!(max_side, 4);
+        }
+        _ => panic!("expected `FrameTooLarge` error"),
+    }
+    recorder.add_frame(solid([0, 0, 0, 255])).unwrap();
+    match recorder.add_frame(solid([0, 0, 0, 255])) {
+        Err(GifRecorderError::TooManyFrames { max }) => assert_eq!(max, 1),
+        _ => panic!("expected `TooManyFrames` error"),
+    }
+
+    let mut recorder = GifRecorder::new(100);
+    recorder.add_frame(solid([0, 0, 0, 255])).unwrap();
+    match recorder.add_frame(image::ImageBuffer::new(2, 2)) {
+        Err(GifRecorderError::FrameSizeMismatch { expected, actual }) => {
+            assert_eq!(expected, [4, 4]);
+            assert_eq!(actual, [2, 2]);
+        }
+        _ => panic!("expected `FrameSizeMismatch` error"),
+    }
+}
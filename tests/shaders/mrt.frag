@@ -0,0 +1,16 @@
+// Writes the vertex color to both of two color targets. Used by the multiple render target tests.
+//
+// Compile with: `glslangValidator -V -o mrt_frag.spv mrt.frag`
+
+#version 450
+
+layout(location = 0) in vec4 v_color;
+layout(location = 1) in vec2 v_tex_coords;
+
+layout(location = 0) out vec4 f_color0;
+layout(location = 1) out vec4 f_color1;
+
+void main() {
+    f_color0 = v_color;
+    f_color1 = v_color;
+}
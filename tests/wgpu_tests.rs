@@ -0,0 +1,373 @@
+use nannou::wgpu::{self, ToneMapOperator};
+
+mod common;
+
+#[test]
+fn tone_map_test() {
+    let hdr = 4.0;
+    let exposure = 1.0;
+
+    let [r, _, _] = ToneMapOperator::Reinhard.map_rgb([hdr, hdr, hdr], exposure);
+    assert!(r >= 0.0 && r <= 1.0);
+    assert_eq!(r, 4.0 / 5.0);
+
+    let [r, _, _] = ToneMapOperator::AcesFilmic.map_rgb([hdr, hdr, hdr], exposure);
+    assert!(r >= 0.0 && r <= 1.0);
+    let expected = (hdr * (2.51 * hdr + 0.03)) / (hdr * (2.43 * hdr + 0.59) + 0.14);
+    assert_eq!(r, expected.min(1.0));
+}
+
+#[test]
+fn render_target_usage_test() {
+    let usage = wgpu::TextureBuilder::RENDER_TARGET_USAGE;
+    assert!(usage.contains(wgpu::TextureUsage::OUTPUT_ATTACHMENT));
+    assert!(usage.contains(wgpu::TextureUsage::SAMPLED));
+    assert!(usage.contains(wgpu::TextureUsage::COPY_SRC));
+}
+
+#[test]
+fn stereo_eye_src_rect_test() {
+    let left = wgpu::Eye::Left.src_rect();
+    let right = wgpu::Eye::Right.src_rect();
+    assert_eq!((left.left(), left.right()), (0.0, 0.5));
+    assert_eq!((right.left(), right.right()), (0.5, 1.0));
+    assert_eq!((left.bottom(), left.top()), (0.0, 1.0));
+    assert_eq!((right.bottom(), right.top()), (0.0, 1.0));
+}
+
+#[test]
+fn unpremultiply_alpha_test() {
+    let assert_near = |a: [u8; 4], b: [u8; 4]| {
+        for (&a, &b) in a.iter().zip(b.iter()) {
+            assert!((a as i32 - b as i32).abs() <= 1, "{:?} != {:?}", a, b);
+        }
+    };
+
+    // Fully opaque and fully transparent pixels are left unchanged.
+    let pixels = [[200, 100, 0, 255], [0, 0, 0, 0]];
+    let mut image =
+        nannou::image::ImageBuffer::from_fn(2, 1, |x, _| nannou::image::Rgba(pixels[x as usize]));
+    wgpu::unpremultiply_alpha(&mut image);
+    for (pixel, &expected) in image.pixels().zip(pixels.iter()) {
+        assert_eq!(pixel.0, expected);
+    }
+
+    // Half-transparent white and mid-grey, premultiplied in linear space and encoded as sRGB.
+    let mut image = nannou::image::ImageBuffer::from_fn(2, 1, |x, _| match x {
+        0 => nannou::image::Rgba([188, 188, 188, 128]),
+        _ => nannou::image::Rgba([137, 137, 137, 128]),
+    });
+    wgpu::unpremultiply_alpha(&mut image);
+    assert_near(image.get_pixel(0, 0).0, [255, 255, 255, 128]);
+    assert_near(image.get_pixel(1, 0).0, [188, 188, 188, 128]);
+}
+
+#[test]
+fn capture_unpremultiply_alpha_test() {
+    use nannou::color::lin_srgba;
+    use nannou::draw::backend::wgpu::Renderer;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    let (device, mut queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+
+    // Half-transparent white drawn over a transparent background.
+    let size = [64, 64];
+    let format = wgpu::TextureCapturer::DST_FORMAT;
+    let texture = wgpu::Texture::render_target(&device, size, format, 1);
+    let view = texture.create_default_view();
+    let draw = nannou::Draw::new();
+    draw.background().color(lin_srgba(0.0, 0.0, 0.0, 0.0));
+    draw.rect()
+        .w_h(64.0, 64.0)
+        .color(lin_srgba(1.0, 1.0, 1.0, 0.5));
+    let mut renderer = Renderer::new(&device, size, 1, format);
+    let desc = wgpu::CommandEncoderDescriptor::default();
+    let mut encoder = device.create_command_encoder(&desc);
+    renderer.encode_render_pass(&device, &mut encoder, &draw, 1.0, size, &view, None);
+
+    let capturer = wgpu::TextureCapturer::new().unpremultiply_alpha(true);
+    let snapshot = capturer.capture(&device, &mut encoder, &texture);
+    queue.submit(&[encoder.finish()]);
+    let image = Rc::new(RefCell::new(None));
+    let image2 = image.clone();
+    snapshot.read(move |result| {
+        let mapped = result.expect("failed to map snapshot");
+        // The draw output itself holds premultiplied color.
+        let [r, _, _, a] = mapped.get_pixel(32, 32).0;
+        assert!((r as i32 - 188).abs() <= 1 && (a as i32 - 128).abs() <= 1);
+        *image2.borrow_mut() = Some(mapped.to_owned());
+    });
+    device.poll(true);
+
+    let image = image.borrow_mut().take().expect("snapshot was not read");
+    for pixel in image.pixels() {
+        let [r, g, b, a] = pixel.0;
+        assert!(r >= 254 && g >= 254 && b >= 254, "{:?}", pixel);
+        assert!((a as i32 - 128).abs() <= 1, "{:?}", pixel);
+    }
+}
+
+#[test]
+fn srgb_conversion_test() {
+    use wgpu::{SrgbConversion, TextureFormat};
+    let srgb = TextureFormat::Rgba8UnormSrgb;
+    let linear = TextureFormat::Rgba8Unorm;
+    assert!(wgpu::texture_format_is_srgb(srgb));
+    assert!(!wgpu::texture_format_is_srgb(linear));
+    assert_eq!(SrgbConversion::between(srgb, srgb), SrgbConversion::None);
+    assert_eq!(
+        SrgbConversion::between(srgb, linear),
+        SrgbConversion::Decode
+    );
+    assert_eq!(
+        SrgbConversion::between(linear, srgb),
+        SrgbConversion::Encode
+    );
+    assert_eq!(
+        SrgbConversion::between(TextureFormat::Rgba16Float, linear),
+        SrgbConversion::None
+    );
+}
+
+#[test]
+fn dont_care_load_op_test() {
+    // There is no way to load undefined contents, so clearing is the cheapest available option.
+    let load_op = wgpu::RenderPassColorAttachmentDescriptorBuilder::DONT_CARE_LOAD_OP;
+    assert_eq!(load_op, wgpu::LoadOp::Clear);
+    assert_ne!(load_op, wgpu::LoadOp::Load);
+}
+
+#[test]
+fn color_conversion_test() {
+    use wgpu::ColorConversion;
+    assert_eq!(ColorConversion::default(), ColorConversion::None);
+    assert_eq!(ColorConversion::None.convert(0.25), 0.25);
+
+    // The endpoints are preserved while mid-tones are brightened when encoding.
+    let encode = ColorConversion::LinearToSrgb;
+    let decode = ColorConversion::SrgbToLinear;
+    assert_eq!(encode.convert(0.0), 0.0);
+    assert!((encode.convert(1.0) - 1.0).abs() < 1e-6);
+    assert!((encode.convert(0.214) - 0.5).abs() < 1e-3);
+
+    // Decoding reverses encoding, while out of range values are clamped.
+    for &x in &[0.001, 0.1, 0.5, 0.9] {
+        assert!((decode.convert(encode.convert(x)) - x).abs() < 1e-5);
+    }
+    assert!((encode.convert(4.0) - 1.0).abs() < 1e-6);
+    assert_eq!(decode.convert(-1.0), 0.0);
+}
+
+#[test]
+fn composite_mode_test() {
+    use wgpu::CompositeMode;
+    let gray = [0.5, 0.5, 0.5, 1.0];
+
+    // Screen brightens a gray base with a gray overlay.
+    let [r, g, b, a] = CompositeMode::Screen.composite(gray, gray);
+    assert_eq!([r, g, b, a], [0.75, 0.75, 0.75, 1.0]);
+
+    // Multiply darkens, while normal simply replaces.
+    assert_eq!(CompositeMode::Multiply.composite(gray, gray)[0], 0.25);
+    assert_eq!(CompositeMode::Normal.composite(gray, gray), gray);
+    assert_eq!(CompositeMode::Add.composite(gray, gray)[0], 1.0);
+
+    // Overlay multiplies dark bases and screens light bases.
+    assert_eq!(CompositeMode::Overlay.blend(0.25, 0.5), 0.25);
+    assert_eq!(CompositeMode::Overlay.blend(0.75, 0.5), 0.75);
+
+    // A transparent overlay leaves the base unchanged.
+    let clear = [1.0, 1.0, 1.0, 0.0];
+    assert_eq!(CompositeMode::Screen.composite(gray, clear), gray);
+}
+
+#[test]
+fn colormap_test() {
+    use wgpu::Colormap;
+    let approx_eq = |a: [f32; 3], b: [f32; 3]| a.iter().zip(&b).all(|(a, b)| (a - b).abs() < 1e-5);
+
+    // The endpoints of a 0 to 1 gradient map to the ends of viridis, from purple to yellow.
+    let viridis = Colormap::Viridis;
+    assert!(approx_eq(viridis.map(0.0), [0.267004, 0.004874, 0.329415]));
+    assert!(approx_eq(viridis.map(1.0), [0.993248, 0.906157, 0.143936]));
+
+    // Values outside the range are clamped.
+    assert_eq!(viridis.map(-1.0), viridis.map(0.0));
+    assert_eq!(viridis.map(2.0), viridis.map(1.0));
+
+    // Jet runs from dark blue through green to dark red.
+    let jet = Colormap::Jet;
+    assert_eq!(jet.map(0.0), [0.0, 0.0, 0.5]);
+    assert_eq!(jet.map(0.5), [0.5, 1.0, 0.5]);
+    assert_eq!(jet.map(1.0), [0.5, 0.0, 0.0]);
+
+    // Magma brightens monotonically.
+    let lum = |[r, g, b]: [f32; 3]| r + g + b;
+    let steps: Vec<f32> = (0..=10)
+        .map(|i| lum(Colormap::Magma.map(i as f32 / 10.0)))
+        .collect();
+    assert!(steps.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn reshaper_transformed_quad_test() {
+    use nannou::math::{Deg, Matrix3};
+    // Scale the quad down and rotate it by 45 degrees so that its corners land on the axes.
+    let scale = 1.0 / 2f32.sqrt();
+    let transform = Matrix3::from_angle_z(Deg(45.0)) * Matrix3::from_scale(scale);
+    let corners = wgpu::TextureReshaper::transformed_quad(transform);
+    let expected = [[0.0, -1.0], [-1.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+    for (corner, &[x, y]) in corners.iter().zip(expected.iter()) {
+        assert!((corner.x - x).abs() < 1e-6, "{:?} != {:?}", corner, [x, y]);
+        assert!((corner.y - y).abs() < 1e-6, "{:?} != {:?}", corner, [x, y]);
+    }
+
+    // The identity transform leaves the quad covering the destination.
+    let corners = wgpu::TextureReshaper::transformed_quad(Matrix3::from_scale(1.0));
+    assert_eq!(corners[0], nannou::geom::pt2(-1.0, -1.0));
+    assert_eq!(corners[3], nannou::geom::pt2(1.0, 1.0));
+}
+
+#[test]
+fn pipeline_without_fragment_shader_test() {
+    let (device, _queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    // A depth-only pipeline specifies neither a fragment shader nor any color states.
+    let vs_mod = common::shader_module(&device, common::DRAW_VERT_SPV);
+    let layouts: &[&wgpu::BindGroupLayout] = &[];
+    wgpu::RenderPipelineBuilder::from_layout_descriptor(layouts, &vs_mod)
+        .add_vertex_buffer::<nannou::draw::backend::wgpu::Vertex>()
+        .depth_format(wgpu::TextureFormat::Depth32Float)
+        .build(&device);
+}
+
+#[test]
+fn render_pipeline_mrt_test() {
+    let (device, _queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let vs_mod = common::shader_module(&device, common::DRAW_VERT_SPV);
+    let fs_mod = common::shader_module(&device, include_bytes!("shaders/mrt_frag.spv"));
+    let layout = wgpu::BindGroupLayoutBuilder::new().build(&device);
+    // A scene target alongside an additive bloom threshold target of a different format.
+    let targets = [
+        wgpu::ColorStateDescriptor {
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            ..wgpu::RenderPipelineBuilder::DEFAULT_COLOR_STATE
+        },
+        wgpu::ColorStateDescriptor {
+            format: wgpu::TextureFormat::Rgba16Float,
+            color_blend: wgpu::BlendDescriptor {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+            ..wgpu::RenderPipelineBuilder::DEFAULT_COLOR_STATE
+        },
+    ];
+    nannou::draw::backend::wgpu::render_pipeline_mrt(
+        &device,
+        &layout,
+        &vs_mod,
+        &fs_mod,
+        &targets,
+        wgpu::TextureFormat::Depth32Float,
+        1,
+    );
+}
+
+#[test]
+fn render_pipeline_mrt_write_mask_test() {
+    use nannou::draw::backend::wgpu::Vertex;
+    let (device, mut queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let vs_mod = common::shader_module(&device, common::DRAW_VERT_SPV);
+    let fs_mod = common::shader_module(&device, include_bytes!("shaders/mrt_frag.spv"));
+    let layout = wgpu::BindGroupLayoutBuilder::new().build(&device);
+    let bind_group = wgpu::BindGroupBuilder::new().build(&device, &layout);
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let depth_format = wgpu::TextureFormat::Depth32Float;
+    // Write color to attachment 0 while leaving attachment 1 untouched.
+    let targets = [
+        wgpu::ColorStateDescriptor {
+            format,
+            ..wgpu::RenderPipelineBuilder::DEFAULT_COLOR_STATE
+        },
+        wgpu::ColorStateDescriptor {
+            format,
+            write_mask: wgpu::ColorWrite::empty(),
+            ..wgpu::RenderPipelineBuilder::DEFAULT_COLOR_STATE
+        },
+    ];
+    let pipeline = nannou::draw::backend::wgpu::render_pipeline_mrt(
+        &device,
+        &layout,
+        &vs_mod,
+        &fs_mod,
+        &targets,
+        depth_format,
+        1,
+    );
+
+    let size = [64, 64];
+    let texture0 = wgpu::Texture::render_target(&device, size, format, 1);
+    let texture1 = wgpu::Texture::render_target(&device, size, format, 1);
+    let depth = wgpu::TextureBuilder::new()
+        .size(size)
+        .format(depth_format)
+        .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT)
+        .build(&device);
+    let (view0, view1) = (
+        texture0.create_default_view(),
+        texture1.create_default_view(),
+    );
+    let depth_view = depth.create_default_view();
+
+    // A red triangle covering the whole target.
+    let red = [1.0, 0.0, 0.0, 1.0];
+    let vertices = [[-1.0, -1.0, 0.0], [3.0, -1.0, 0.0], [-1.0, 3.0, 0.0]];
+    let vertices: Vec<Vertex> = vertices
+        .iter()
+        .map(|&position| Vertex {
+            position,
+            color: red,
+            tex_coords: [0.0, 0.0],
+        })
+        .collect();
+    let vertex_buffer = device
+        .create_buffer_mapped(vertices.len(), wgpu::BufferUsage::VERTEX)
+        .fill_from_slice(&vertices[..]);
+
+    let desc = wgpu::CommandEncoderDescriptor::default();
+    let mut encoder = device.create_command_encoder(&desc);
+    {
+        let green = wgpu::Color {
+            r: 0.0,
+            g: 1.0,
+            b: 0.0,
+            a: 1.0,
+        };
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(&view0, |color| color)
+            .color_attachment(&view1, |color| color.clear_color(green))
+            .depth_stencil_attachment(&depth_view, |depth| depth)
+            .begin(&mut encoder);
+        render_pass.set_pipeline(&pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_vertex_buffers(0, &[(&vertex_buffer, 0)]);
+        render_pass.draw(0..vertices.len() as u32, 0..1);
+    }
+    let bytes0 = common::read_texture(&device, &mut queue, encoder, &texture0);
+    let encoder = device.create_command_encoder(&desc);
+    let bytes1 = common::read_texture(&device, &mut queue, encoder, &texture1);
+    assert!(bytes0.chunks_exact(4).all(|px| px == [255, 0, 0, 255]));
+    assert!(bytes1.chunks_exact(4).all(|px| px == [0, 255, 0, 255]));
+}